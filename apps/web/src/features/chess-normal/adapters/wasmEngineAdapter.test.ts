@@ -1,5 +1,5 @@
 import { beforeEach, describe, expect, it, vi } from "vitest";
-import type { GameStateDTO } from "@chess/contracts";
+import type { GameStateDTO, MoveResultDTO } from "@chess/contracts";
 import type { WasmGameLike } from "@chess/engine-wasm";
 
 const { createWasmGameMock } = vi.hoisted(() => ({
@@ -30,10 +30,32 @@ function createState(activeColor: GameStateDTO["activeColor"]): GameStateDTO {
   };
 }
 
+function createMoveResult(activeColor: GameStateDTO["activeColor"]): MoveResultDTO {
+  return {
+    state: createState(activeColor),
+    details: {
+      movedPiece: { color: "white", kind: "pawn" },
+      capturedPiece: null,
+      capturedSquare: null,
+      castleRookFrom: null,
+      castleRookTo: null,
+      givesCheck: false,
+      givesMate: false,
+      flags: {
+        capture: false,
+        enPassant: false,
+        castling: false,
+        doublePush: true,
+        promotion: false
+      }
+    }
+  };
+}
+
 function createMockGame(overrides?: Partial<WasmGameLike>): WasmGameLike {
   return {
     legalMoves: vi.fn().mockReturnValue([]),
-    applyMove: vi.fn().mockReturnValue(createState("black")),
+    applyMove: vi.fn().mockReturnValue(createMoveResult("black")),
     state: vi.fn().mockReturnValue(createState("white")),
     ...overrides
   };
@@ -76,7 +98,7 @@ describe("wasmEngineAdapter", () => {
   });
 
   it("rejects illegal moves before calling applyMove", async () => {
-    const applyMove = vi.fn().mockReturnValue(createState("black"));
+    const applyMove = vi.fn().mockReturnValue(createMoveResult("black"));
     const mockGame = createMockGame({
       legalMoves: vi.fn().mockReturnValue([{ from: "e2", to: "e3" }]),
       applyMove
@@ -47,7 +47,7 @@ export async function applyMove(move: MoveDTO): Promise<GameStateDTO> {
     throw new Error(`Illegal move: ${move.from} -> ${move.to}`);
   }
 
-  return game.applyMove(move.from, move.to, move.promotion);
+  return game.applyMove(move.from, move.to, move.promotion).state;
 }
 
 export async function resetGame(): Promise<void> {
@@ -10,6 +10,18 @@ function statusLabel(status: GameStatusDTO): string {
     return `Checkmate - ${status.winner} wins`;
   }
 
+  if (status.type === "draw") {
+    return `Draw (${status.reason.replace(/_/g, " ")})`;
+  }
+
+  if (status.type === "resigned") {
+    return `${status.loser} resigned`;
+  }
+
+  if (status.type === "timeout") {
+    return `${status.loser} lost on time`;
+  }
+
   return `Stalemate (${status.sideToMove} to move)`;
 }
 
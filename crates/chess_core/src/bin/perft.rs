@@ -0,0 +1,39 @@
+use std::process::ExitCode;
+
+use chess_core::perft::perft_divide;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(depth) = args.first().and_then(|depth| depth.parse::<u32>().ok()) else {
+        eprintln!("usage: perft <depth> [fen]");
+        return ExitCode::FAILURE;
+    };
+
+    let fen = if args.len() > 1 {
+        args[1..].join(" ")
+    } else {
+        "startpos".to_string()
+    };
+
+    let state = match chess_core::from_fen(&fen) {
+        Ok(state) => state,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let divide = perft_divide(&state, depth);
+    for (mv, nodes) in &divide {
+        println!("{mv}: {nodes}");
+    }
+
+    let total: u64 = if depth == 0 {
+        1
+    } else {
+        divide.iter().map(|(_, nodes)| nodes).sum()
+    };
+    println!();
+    println!("Nodes searched: {total}");
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,279 @@
+use crate::board::{empty_board, Board, Piece, PieceColor, PieceKind, Square};
+use crate::move_gen::opposite;
+
+// Bit `rank * 8 + file` is set for each square in the set, so a1 is bit 0 and h8 bit 63.
+pub type Bitboard = u64;
+
+pub const RANK_1: Bitboard = 0xff;
+pub const RANK_8: Bitboard = RANK_1 << 56;
+pub const FILE_A: Bitboard = 0x0101_0101_0101_0101;
+
+const PIECE_KINDS: [PieceKind; 6] = [
+    PieceKind::Pawn,
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Rook,
+    PieceKind::Queen,
+    PieceKind::King,
+];
+
+pub fn square_index(square: Square) -> usize {
+    square.rank as usize * 8 + square.file as usize
+}
+
+pub fn index_square(index: usize) -> Square {
+    Square {
+        file: (index % 8) as u8,
+        rank: (index / 8) as u8,
+    }
+}
+
+pub fn square_bit(square: Square) -> Bitboard {
+    1 << square_index(square)
+}
+
+// Yields square indices from a1 towards h8.
+pub fn squares(mut bitboard: Bitboard) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if bitboard == 0 {
+            return None;
+        }
+        let index = bitboard.trailing_zeros() as usize;
+        bitboard &= bitboard - 1;
+        Some(index)
+    })
+}
+
+const fn leaper_attacks(deltas: &[(i8, i8)]) -> [Bitboard; 64] {
+    let mut table = [0; 64];
+    let mut index = 0;
+    while index < 64 {
+        let file = (index % 8) as i8;
+        let rank = (index / 8) as i8;
+        let mut d = 0;
+        while d < deltas.len() {
+            let (df, dr) = deltas[d];
+            let (to_file, to_rank) = (file + df, rank + dr);
+            if to_file >= 0 && to_file < 8 && to_rank >= 0 && to_rank < 8 {
+                table[index] |= 1 << (to_rank * 8 + to_file);
+            }
+            d += 1;
+        }
+        index += 1;
+    }
+    table
+}
+
+const KNIGHT_ATTACKS: [Bitboard; 64] = leaper_attacks(&[
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+]);
+
+const KING_ATTACKS: [Bitboard; 64] = leaper_attacks(&[
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+]);
+
+const PAWN_ATTACKS: [[Bitboard; 64]; 2] = [
+    leaper_attacks(&[(-1, 1), (1, 1)]),
+    leaper_attacks(&[(-1, -1), (1, -1)]),
+];
+
+// The first four directions increase the square index, the last four decrease it.
+const DIRECTIONS: [(i8, i8); 8] = [
+    (0, 1),
+    (1, 0),
+    (1, 1),
+    (-1, 1),
+    (0, -1),
+    (-1, 0),
+    (-1, -1),
+    (1, -1),
+];
+
+const fn ray_table() -> [[Bitboard; 64]; 8] {
+    let mut table = [[0; 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let (df, dr) = DIRECTIONS[direction];
+        let mut index = 0;
+        while index < 64 {
+            let mut file = (index % 8) as i8 + df;
+            let mut rank = (index / 8) as i8 + dr;
+            while file >= 0 && file < 8 && rank >= 0 && rank < 8 {
+                table[direction][index] |= 1 << (rank * 8 + file);
+                file += df;
+                rank += dr;
+            }
+            index += 1;
+        }
+        direction += 1;
+    }
+    table
+}
+
+const RAYS: [[Bitboard; 64]; 8] = ray_table();
+
+const ORTHOGONAL: [usize; 4] = [0, 1, 4, 5];
+const DIAGONAL: [usize; 4] = [2, 3, 6, 7];
+
+// Classical ray attacks: each ray is cut off behind its first blocker.
+fn ray_attacks(index: usize, occupied: Bitboard, directions: [usize; 4]) -> Bitboard {
+    let mut attacks = 0;
+    for direction in directions {
+        let ray = RAYS[direction][index];
+        let blockers = ray & occupied;
+        attacks |= if blockers == 0 {
+            ray
+        } else {
+            let blocker = if direction < 4 {
+                blockers.trailing_zeros()
+            } else {
+                63 - blockers.leading_zeros()
+            };
+            ray ^ RAYS[direction][blocker as usize]
+        };
+    }
+    attacks
+}
+
+pub fn knight_attacks(index: usize) -> Bitboard {
+    KNIGHT_ATTACKS[index]
+}
+
+pub fn king_attacks(index: usize) -> Bitboard {
+    KING_ATTACKS[index]
+}
+
+pub fn pawn_attacks(color: PieceColor, index: usize) -> Bitboard {
+    PAWN_ATTACKS[color.index()][index]
+}
+
+pub fn bishop_attacks(index: usize, occupied: Bitboard) -> Bitboard {
+    ray_attacks(index, occupied, DIAGONAL)
+}
+
+pub fn rook_attacks(index: usize, occupied: Bitboard) -> Bitboard {
+    ray_attacks(index, occupied, ORTHOGONAL)
+}
+
+pub fn queen_attacks(index: usize, occupied: Bitboard) -> Bitboard {
+    bishop_attacks(index, occupied) | rook_attacks(index, occupied)
+}
+
+// Piece placement as one bitboard per color and kind, kept in step with `GameState.board`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pieces: [[Bitboard; 6]; 2],
+    colors: [Bitboard; 2],
+}
+
+impl Position {
+    pub fn empty() -> Self {
+        Self {
+            pieces: [[0; 6]; 2],
+            colors: [0; 2],
+        }
+    }
+
+    pub fn from_board(board: &Board) -> Self {
+        let mut position = Self::empty();
+        for (rank, row) in board.iter().enumerate() {
+            for (file, piece) in row.iter().enumerate() {
+                if let Some(piece) = piece {
+                    position.put(rank * 8 + file, *piece);
+                }
+            }
+        }
+        position
+    }
+
+    pub fn to_board(&self) -> Board {
+        let mut board = empty_board();
+        for index in squares(self.occupied()) {
+            board[index / 8][index % 8] = self.piece_at(index);
+        }
+        board
+    }
+
+    pub fn pieces(&self, color: PieceColor, kind: PieceKind) -> Bitboard {
+        self.pieces[color.index()][kind.index()]
+    }
+
+    pub fn color(&self, color: PieceColor) -> Bitboard {
+        self.colors[color.index()]
+    }
+
+    pub fn occupied(&self) -> Bitboard {
+        self.colors[0] | self.colors[1]
+    }
+
+    pub fn piece_at(&self, index: usize) -> Option<Piece> {
+        let bit = 1 << index;
+        let color = if self.colors[0] & bit != 0 {
+            PieceColor::White
+        } else if self.colors[1] & bit != 0 {
+            PieceColor::Black
+        } else {
+            return None;
+        };
+
+        PIECE_KINDS
+            .into_iter()
+            .find(|kind| self.pieces(color, *kind) & bit != 0)
+            .map(|kind| Piece { color, kind })
+    }
+
+    pub fn put(&mut self, index: usize, piece: Piece) {
+        let bit = 1 << index;
+        self.pieces[piece.color.index()][piece.kind.index()] |= bit;
+        self.colors[piece.color.index()] |= bit;
+    }
+
+    // Adds the piece if it is absent and removes it if it is present.
+    pub fn toggle(&mut self, index: usize, piece: Piece) {
+        let bit = 1 << index;
+        self.pieces[piece.color.index()][piece.kind.index()] ^= bit;
+        self.colors[piece.color.index()] ^= bit;
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Piece> {
+        let piece = self.piece_at(index)?;
+        let bit: Bitboard = 1 << index;
+        self.pieces[piece.color.index()][piece.kind.index()] &= !bit;
+        self.colors[piece.color.index()] &= !bit;
+        Some(piece)
+    }
+
+    pub fn king_square(&self, color: PieceColor) -> Option<usize> {
+        squares(self.pieces(color, PieceKind::King)).next()
+    }
+
+    pub fn attackers_to(&self, index: usize, by_color: PieceColor) -> Bitboard {
+        let occupied = self.occupied();
+        let queens = self.pieces(by_color, PieceKind::Queen);
+
+        (pawn_attacks(opposite(by_color), index) & self.pieces(by_color, PieceKind::Pawn))
+            | (knight_attacks(index) & self.pieces(by_color, PieceKind::Knight))
+            | (king_attacks(index) & self.pieces(by_color, PieceKind::King))
+            | (bishop_attacks(index, occupied)
+                & (self.pieces(by_color, PieceKind::Bishop) | queens))
+            | (rook_attacks(index, occupied) & (self.pieces(by_color, PieceKind::Rook) | queens))
+    }
+
+    pub fn is_attacked_by(&self, index: usize, by_color: PieceColor) -> bool {
+        self.attackers_to(index, by_color) != 0
+    }
+}
@@ -6,6 +6,15 @@ pub enum PieceColor {
     Black,
 }
 
+impl PieceColor {
+    pub fn index(self) -> usize {
+        match self {
+            Self::White => 0,
+            Self::Black => 1,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PieceKind {
     Pawn,
@@ -16,12 +25,61 @@ pub enum PieceKind {
     King,
 }
 
+impl PieceKind {
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_lowercase() {
+            'p' => Some(Self::Pawn),
+            'n' => Some(Self::Knight),
+            'b' => Some(Self::Bishop),
+            'r' => Some(Self::Rook),
+            'q' => Some(Self::Queen),
+            'k' => Some(Self::King),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Pawn => 'p',
+            Self::Knight => 'n',
+            Self::Bishop => 'b',
+            Self::Rook => 'r',
+            Self::Queen => 'q',
+            Self::King => 'k',
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Piece {
     pub color: PieceColor,
     pub kind: PieceKind,
 }
 
+impl Piece {
+    pub fn from_fen_char(c: char) -> Option<Self> {
+        let kind = PieceKind::from_char(c)?;
+        let color = if c.is_ascii_uppercase() {
+            PieceColor::White
+        } else {
+            PieceColor::Black
+        };
+        Some(Self { color, kind })
+    }
+
+    pub fn to_fen_char(self) -> char {
+        let c = self.kind.to_char();
+        match self.color {
+            PieceColor::White => c.to_ascii_uppercase(),
+            PieceColor::Black => c,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Square {
     pub file: u8,
@@ -71,16 +129,14 @@ pub fn empty_board() -> Board {
 pub fn standard_start_board() -> Board {
     let mut board = empty_board();
 
-    for file in 0..8 {
-        board[1][file] = Some(Piece {
-            color: PieceColor::White,
-            kind: PieceKind::Pawn,
-        });
-        board[6][file] = Some(Piece {
-            color: PieceColor::Black,
-            kind: PieceKind::Pawn,
-        });
-    }
+    board[1] = [Some(Piece {
+        color: PieceColor::White,
+        kind: PieceKind::Pawn,
+    }); 8];
+    board[6] = [Some(Piece {
+        color: PieceColor::Black,
+        kind: PieceKind::Pawn,
+    }); 8];
 
     let back_rank = [
         PieceKind::Rook,
@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::board::PieceColor;
+use crate::game::{DrawReason, GameState, GameStatus};
+use crate::move_gen::opposite;
+use crate::rules::can_win_on_time;
+
+// All durations and timestamps are milliseconds. Timestamps come from the caller,
+// so the clock never reads the wall clock itself.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeBonus {
+    None,
+    // Added after every move (Fischer).
+    Increment(u64),
+    // Time used is refunded after the move, up to the delay.
+    Bronstein(u64),
+    // The clock only starts counting once the delay has elapsed.
+    SimpleDelay(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeControlStage {
+    // `None` means the stage lasts for the rest of the game.
+    pub moves: Option<u32>,
+    pub base_ms: u64,
+    pub bonus: TimeBonus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeControl {
+    pub stages: Vec<TimeControlStage>,
+}
+
+impl TimeControl {
+    pub fn new(stages: Vec<TimeControlStage>) -> Self {
+        Self { stages }
+    }
+
+    pub fn sudden_death(base_ms: u64) -> Self {
+        Self::single(base_ms, TimeBonus::None)
+    }
+
+    pub fn fischer(base_ms: u64, increment_ms: u64) -> Self {
+        Self::single(base_ms, TimeBonus::Increment(increment_ms))
+    }
+
+    pub fn bronstein(base_ms: u64, delay_ms: u64) -> Self {
+        Self::single(base_ms, TimeBonus::Bronstein(delay_ms))
+    }
+
+    pub fn simple_delay(base_ms: u64, delay_ms: u64) -> Self {
+        Self::single(base_ms, TimeBonus::SimpleDelay(delay_ms))
+    }
+
+    fn single(base_ms: u64, bonus: TimeBonus) -> Self {
+        Self::new(vec![TimeControlStage {
+            moves: None,
+            base_ms,
+            bonus,
+        }])
+    }
+
+    fn stage(&self, index: usize) -> TimeControlStage {
+        // A final stage with a move count repeats, as in "40/120" classical controls.
+        self.stages[index.min(self.stages.len() - 1)]
+    }
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum ClockError {
+    #[error("time control has no stages")]
+    NoStages,
+    #[error("clock is not running")]
+    NotRunning,
+    #[error("clock is already running")]
+    AlreadyRunning,
+    #[error("timestamp is earlier than the start of the current turn")]
+    TimeWentBackwards,
+    #[error("{0:?} has run out of time")]
+    Flagged(PieceColor),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Turn {
+    color: PieceColor,
+    started_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameClock {
+    time_control: TimeControl,
+    remaining: [u64; 2],
+    stage: [usize; 2],
+    moves_in_stage: [u32; 2],
+    turn: Option<Turn>,
+    flagged: Option<PieceColor>,
+}
+
+impl GameClock {
+    pub fn new(time_control: TimeControl) -> Result<Self, ClockError> {
+        let first = time_control.stages.first().ok_or(ClockError::NoStages)?;
+        Ok(Self {
+            remaining: [first.base_ms; 2],
+            time_control,
+            stage: [0; 2],
+            moves_in_stage: [0; 2],
+            turn: None,
+            flagged: None,
+        })
+    }
+
+    pub fn time_control(&self) -> &TimeControl {
+        &self.time_control
+    }
+
+    pub fn active_color(&self) -> Option<PieceColor> {
+        self.turn.map(|turn| turn.color)
+    }
+
+    pub fn start(&mut self, color: PieceColor, now_ms: u64) -> Result<(), ClockError> {
+        if self.turn.is_some() {
+            return Err(ClockError::AlreadyRunning);
+        }
+        if let Some(flagged) = self.flagged {
+            return Err(ClockError::Flagged(flagged));
+        }
+
+        self.turn = Some(Turn {
+            color,
+            started_at: now_ms,
+        });
+        Ok(())
+    }
+
+    // Stops the clock without applying any bonus, e.g. when the game ends.
+    pub fn stop(&mut self, now_ms: u64) -> Result<(), ClockError> {
+        let turn = self.turn.ok_or(ClockError::NotRunning)?;
+        let elapsed = self.elapsed(turn, now_ms)?;
+        let color = turn.color.index();
+        self.remaining[color] = self.remaining_after(turn.color, elapsed);
+        self.turn = None;
+        Ok(())
+    }
+
+    // Called when the player to move completes their move; hands the clock to the opponent.
+    pub fn press(&mut self, now_ms: u64) -> Result<(), ClockError> {
+        let turn = self.turn.ok_or(ClockError::NotRunning)?;
+        if let Some(flagged) = self.flagged(now_ms) {
+            self.flagged = Some(flagged);
+            self.turn = None;
+            return Err(ClockError::Flagged(flagged));
+        }
+
+        let elapsed = self.elapsed(turn, now_ms)?;
+        let color = turn.color.index();
+        let stage = self.time_control.stage(self.stage[color]);
+
+        let mut remaining = self.remaining_after(turn.color, elapsed);
+        remaining += match stage.bonus {
+            TimeBonus::None | TimeBonus::SimpleDelay(_) => 0,
+            TimeBonus::Increment(increment) => increment,
+            TimeBonus::Bronstein(delay) => elapsed.min(delay),
+        };
+
+        self.moves_in_stage[color] += 1;
+        if stage.moves == Some(self.moves_in_stage[color]) {
+            self.stage[color] += 1;
+            self.moves_in_stage[color] = 0;
+            remaining += self.time_control.stage(self.stage[color]).base_ms;
+        }
+
+        self.remaining[color] = remaining;
+        self.turn = Some(Turn {
+            color: opposite(turn.color),
+            started_at: now_ms,
+        });
+        Ok(())
+    }
+
+    pub fn remaining(&self, color: PieceColor, now_ms: u64) -> u64 {
+        match self.turn {
+            Some(turn) if turn.color == color => {
+                let elapsed = now_ms.saturating_sub(turn.started_at);
+                self.remaining_after(color, elapsed)
+            }
+            _ => self.remaining[color.index()],
+        }
+    }
+
+    pub fn flagged(&self, now_ms: u64) -> Option<PieceColor> {
+        self.flagged.or_else(|| {
+            let turn = self.turn?;
+            (self.remaining(turn.color, now_ms) == 0).then_some(turn.color)
+        })
+    }
+
+    fn elapsed(&self, turn: Turn, now_ms: u64) -> Result<u64, ClockError> {
+        now_ms
+            .checked_sub(turn.started_at)
+            .ok_or(ClockError::TimeWentBackwards)
+    }
+
+    fn remaining_after(&self, color: PieceColor, elapsed: u64) -> u64 {
+        let stage = self.time_control.stage(self.stage[color.index()]);
+        let charged = match stage.bonus {
+            TimeBonus::SimpleDelay(delay) => elapsed.saturating_sub(delay),
+            _ => elapsed,
+        };
+        self.remaining[color.index()].saturating_sub(charged)
+    }
+}
+
+// The side whose flag fell loses, unless the opponent could never mate.
+pub fn timeout_status(state: &GameState, flagged: PieceColor) -> GameStatus {
+    if can_win_on_time(state, opposite(flagged)) {
+        GameStatus::Timeout(flagged)
+    } else {
+        GameStatus::Draw(DrawReason::TimeoutVsInsufficientMaterial)
+    }
+}
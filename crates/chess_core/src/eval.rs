@@ -0,0 +1,432 @@
+use serde::{Deserialize, Serialize};
+
+use crate::bitboard::{
+    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks,
+    squares, Bitboard, Position, FILE_A,
+};
+use crate::board::{PieceColor, PieceKind};
+use crate::game::GameState;
+use crate::move_gen::opposite;
+
+// Phase runs from `MAX_PHASE` with all minor and major pieces on the board down to
+// 0 with none, and blends the middlegame and endgame halves of every term.
+pub const MAX_PHASE: i32 = 24;
+
+// Scores in one term, from white's point of view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TermScore {
+    pub mg: i32,
+    pub eg: i32,
+}
+
+impl TermScore {
+    pub fn tapered(self, phase: i32) -> i32 {
+        (self.mg * phase + self.eg * (MAX_PHASE - phase)) / MAX_PHASE
+    }
+
+    fn add(&mut self, color: PieceColor, mg: i32, eg: i32) {
+        let sign = match color {
+            PieceColor::White => 1,
+            PieceColor::Black => -1,
+        };
+        self.mg += sign * mg;
+        self.eg += sign * eg;
+    }
+}
+
+// `total` is in centipawns for the side to move; the terms are from white's side
+// so they read the same whichever color is to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvalBreakdown {
+    pub phase: i32,
+    pub material: TermScore,
+    pub piece_squares: TermScore,
+    pub mobility: TermScore,
+    pub pawn_structure: TermScore,
+    pub king_safety: TermScore,
+    pub bishop_pair: TermScore,
+    pub total: i32,
+}
+
+impl EvalBreakdown {
+    fn terms(&self) -> [TermScore; 6] {
+        [
+            self.material,
+            self.piece_squares,
+            self.mobility,
+            self.pawn_structure,
+            self.king_safety,
+            self.bishop_pair,
+        ]
+    }
+
+    // Tapered total from white's point of view.
+    pub fn white_score(&self) -> i32 {
+        self.terms()
+            .iter()
+            .map(|term| term.tapered(self.phase))
+            .sum()
+    }
+}
+
+const KINDS: [PieceKind; 6] = [
+    PieceKind::Pawn,
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Rook,
+    PieceKind::Queen,
+    PieceKind::King,
+];
+
+fn material(kind: PieceKind) -> (i32, i32) {
+    match kind {
+        PieceKind::Pawn => (82, 94),
+        PieceKind::Knight => (337, 281),
+        PieceKind::Bishop => (365, 297),
+        PieceKind::Rook => (477, 512),
+        PieceKind::Queen => (1025, 936),
+        PieceKind::King => (0, 0),
+    }
+}
+
+// A single value per piece for move ordering, taken from the middlegame material.
+pub(crate) fn piece_value(kind: PieceKind) -> i32 {
+    material(kind).0
+}
+
+fn phase_weight(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::Knight | PieceKind::Bishop => 1,
+        PieceKind::Rook => 2,
+        PieceKind::Queen => 4,
+        PieceKind::Pawn | PieceKind::King => 0,
+    }
+}
+
+// Per-move mobility bonus, middlegame and endgame.
+fn mobility_weight(kind: PieceKind) -> (i32, i32) {
+    match kind {
+        PieceKind::Knight => (4, 4),
+        PieceKind::Bishop => (5, 5),
+        PieceKind::Rook => (2, 4),
+        PieceKind::Queen => (1, 2),
+        PieceKind::Pawn | PieceKind::King => (0, 0),
+    }
+}
+
+const DOUBLED_PAWN: (i32, i32) = (-10, -20);
+const ISOLATED_PAWN: (i32, i32) = (-15, -10);
+// Indexed by how far the pawn has advanced, 0 being its own back rank.
+const PASSED_PAWN_MG: [i32; 8] = [0, 5, 10, 15, 25, 40, 60, 0];
+const PASSED_PAWN_EG: [i32; 8] = [0, 10, 15, 25, 45, 70, 110, 0];
+const BISHOP_PAIR: (i32, i32) = (30, 50);
+const PAWN_SHIELD: [i32; 2] = [12, 6];
+const KING_ZONE_ATTACK: i32 = -8;
+const OPEN_FILE_NEAR_KING: i32 = -15;
+
+// Piece-square tables are laid out as seen from white's side, a8 first.
+#[rustfmt::skip]
+const PAWN_MG: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+    50,  50,  50,  50,  50,  50,  50,  50,
+    10,  10,  20,  30,  30,  20,  10,  10,
+     5,   5,  10,  25,  25,  10,   5,   5,
+     0,   0,   0,  20,  20,   0,   0,   0,
+     5,  -5, -10,   0,   0, -10,  -5,   5,
+     5,  10,  10, -20, -20,  10,  10,   5,
+     0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const PAWN_EG: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+    60,  60,  60,  60,  60,  60,  60,  60,
+    40,  40,  40,  40,  40,  40,  40,  40,
+    25,  25,  25,  25,  25,  25,  25,  25,
+    15,  15,  15,  15,  15,  15,  15,  15,
+     5,   5,   5,   5,   5,   5,   5,   5,
+     0,   0,   0,   0,   0,   0,   0,   0,
+     0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const KNIGHT: [i32; 64] = [
+   -50, -40, -30, -30, -30, -30, -40, -50,
+   -40, -20,   0,   0,   0,   0, -20, -40,
+   -30,   0,  10,  15,  15,  10,   0, -30,
+   -30,   5,  15,  20,  20,  15,   5, -30,
+   -30,   0,  15,  20,  20,  15,   0, -30,
+   -30,   5,  10,  15,  15,  10,   5, -30,
+   -40, -20,   0,   5,   5,   0, -20, -40,
+   -50, -40, -30, -30, -30, -30, -40, -50,
+];
+
+#[rustfmt::skip]
+const BISHOP: [i32; 64] = [
+   -20, -10, -10, -10, -10, -10, -10, -20,
+   -10,   0,   0,   0,   0,   0,   0, -10,
+   -10,   0,   5,  10,  10,   5,   0, -10,
+   -10,   5,   5,  10,  10,   5,   5, -10,
+   -10,   0,  10,  10,  10,  10,   0, -10,
+   -10,  10,  10,  10,  10,  10,  10, -10,
+   -10,   5,   0,   0,   0,   0,   5, -10,
+   -20, -10, -10, -10, -10, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const ROOK: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+     5,  10,  10,  10,  10,  10,  10,   5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+     0,   0,   0,   5,   5,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const QUEEN: [i32; 64] = [
+   -20, -10, -10,  -5,  -5, -10, -10, -20,
+   -10,   0,   0,   0,   0,   0,   0, -10,
+   -10,   0,   5,   5,   5,   5,   0, -10,
+    -5,   0,   5,   5,   5,   5,   0,  -5,
+     0,   0,   5,   5,   5,   5,   0,  -5,
+   -10,   5,   5,   5,   5,   5,   0, -10,
+   -10,   0,   5,   0,   0,   0,   0, -10,
+   -20, -10, -10,  -5,  -5, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const KING_MG: [i32; 64] = [
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -20, -30, -30, -40, -40, -30, -30, -20,
+   -10, -20, -20, -20, -20, -20, -20, -10,
+    20,  20,   0,   0,   0,   0,  20,  20,
+    20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+#[rustfmt::skip]
+const KING_EG: [i32; 64] = [
+   -50, -40, -30, -20, -20, -30, -40, -50,
+   -30, -20, -10,   0,   0, -10, -20, -30,
+   -30, -10,  20,  30,  30,  20, -10, -30,
+   -30, -10,  30,  40,  40,  30, -10, -30,
+   -30, -10,  30,  40,  40,  30, -10, -30,
+   -30, -10,  20,  30,  30,  20, -10, -30,
+   -30, -30,   0,   0,   0,   0, -30, -30,
+   -50, -30, -30, -30, -30, -30, -30, -50,
+];
+
+fn piece_square_tables(kind: PieceKind) -> (&'static [i32; 64], &'static [i32; 64]) {
+    match kind {
+        PieceKind::Pawn => (&PAWN_MG, &PAWN_EG),
+        PieceKind::Knight => (&KNIGHT, &KNIGHT),
+        PieceKind::Bishop => (&BISHOP, &BISHOP),
+        PieceKind::Rook => (&ROOK, &ROOK),
+        PieceKind::Queen => (&QUEEN, &QUEEN),
+        PieceKind::King => (&KING_MG, &KING_EG),
+    }
+}
+
+// Index into a table laid out a8-first; black reads it mirrored.
+fn table_index(color: PieceColor, square: usize) -> usize {
+    match color {
+        PieceColor::White => square ^ 56,
+        PieceColor::Black => square,
+    }
+}
+
+// How many ranks a piece on `square` has advanced from its own back rank.
+fn relative_rank(color: PieceColor, square: usize) -> usize {
+    match color {
+        PieceColor::White => square / 8,
+        PieceColor::Black => 7 - square / 8,
+    }
+}
+
+fn file_mask(file: usize) -> Bitboard {
+    FILE_A << file
+}
+
+fn adjacent_files(file: usize) -> Bitboard {
+    let left = if file > 0 { file_mask(file - 1) } else { 0 };
+    let right = if file < 7 { file_mask(file + 1) } else { 0 };
+    left | right
+}
+
+// Squares strictly in front of `square` from `color`'s side, on any file.
+fn ranks_ahead(color: PieceColor, square: usize) -> Bitboard {
+    let rank = square / 8;
+    match color {
+        PieceColor::White if rank < 7 => !0 << (8 * (rank + 1)),
+        PieceColor::Black if rank > 0 => !0 >> (8 * (8 - rank)),
+        _ => 0,
+    }
+}
+
+fn pawn_attack_span(position: &Position, color: PieceColor) -> Bitboard {
+    squares(position.pieces(color, PieceKind::Pawn))
+        .fold(0, |span, square| span | pawn_attacks(color, square))
+}
+
+fn attacks_from(kind: PieceKind, color: PieceColor, square: usize, occupied: Bitboard) -> Bitboard {
+    match kind {
+        PieceKind::Pawn => pawn_attacks(color, square),
+        PieceKind::Knight => knight_attacks(square),
+        PieceKind::Bishop => bishop_attacks(square, occupied),
+        PieceKind::Rook => rook_attacks(square, occupied),
+        PieceKind::Queen => queen_attacks(square, occupied),
+        PieceKind::King => king_attacks(square),
+    }
+}
+
+fn evaluate_pieces(position: &Position, breakdown: &mut EvalBreakdown) {
+    let occupied = position.occupied();
+
+    for color in [PieceColor::White, PieceColor::Black] {
+        let own = position.color(color);
+        let mobility_area = !own & !pawn_attack_span(position, opposite(color));
+
+        for kind in KINDS {
+            let pieces = position.pieces(color, kind);
+            let (material_mg, material_eg) = material(kind);
+            let (table_mg, table_eg) = piece_square_tables(kind);
+            let (mobility_mg, mobility_eg) = mobility_weight(kind);
+
+            for square in squares(pieces) {
+                breakdown.material.add(color, material_mg, material_eg);
+                let index = table_index(color, square);
+                breakdown
+                    .piece_squares
+                    .add(color, table_mg[index], table_eg[index]);
+
+                let moves = (attacks_from(kind, color, square, occupied) & mobility_area)
+                    .count_ones() as i32;
+                breakdown
+                    .mobility
+                    .add(color, moves * mobility_mg, moves * mobility_eg);
+            }
+
+            breakdown.phase += phase_weight(kind) * pieces.count_ones() as i32;
+        }
+
+        if position.pieces(color, PieceKind::Bishop).count_ones() >= 2 {
+            breakdown
+                .bishop_pair
+                .add(color, BISHOP_PAIR.0, BISHOP_PAIR.1);
+        }
+    }
+
+    breakdown.phase = breakdown.phase.min(MAX_PHASE);
+}
+
+fn evaluate_pawns(position: &Position, breakdown: &mut EvalBreakdown) {
+    for color in [PieceColor::White, PieceColor::Black] {
+        let pawns = position.pieces(color, PieceKind::Pawn);
+        let enemy_pawns = position.pieces(opposite(color), PieceKind::Pawn);
+        let term = &mut breakdown.pawn_structure;
+
+        for file in 0..8 {
+            let on_file = (pawns & file_mask(file)).count_ones() as i32;
+            if on_file > 1 {
+                term.add(
+                    color,
+                    DOUBLED_PAWN.0 * (on_file - 1),
+                    DOUBLED_PAWN.1 * (on_file - 1),
+                );
+            }
+            if on_file > 0 && pawns & adjacent_files(file) == 0 {
+                term.add(color, ISOLATED_PAWN.0 * on_file, ISOLATED_PAWN.1 * on_file);
+            }
+        }
+
+        for square in squares(pawns) {
+            let file = square % 8;
+            let front_span = ranks_ahead(color, square) & (file_mask(file) | adjacent_files(file));
+            if enemy_pawns & front_span == 0 {
+                let rank = relative_rank(color, square);
+                term.add(color, PASSED_PAWN_MG[rank], PASSED_PAWN_EG[rank]);
+            }
+        }
+    }
+}
+
+// Only scored in the middlegame half: a king needs cover while queens and rooks
+// are around, and should walk forward once they are gone.
+fn evaluate_king_safety(position: &Position, breakdown: &mut EvalBreakdown) {
+    let occupied = position.occupied();
+
+    for color in [PieceColor::White, PieceColor::Black] {
+        let Some(king) = position.king_square(color) else {
+            continue;
+        };
+        let enemy = opposite(color);
+        let own_pawns = position.pieces(color, PieceKind::Pawn);
+        let all_pawns = own_pawns | position.pieces(enemy, PieceKind::Pawn);
+        let king_file = king % 8;
+        let mut score = 0;
+
+        for file in king_file.saturating_sub(1)..=(king_file + 1).min(7) {
+            if all_pawns & file_mask(file) == 0 {
+                score += OPEN_FILE_NEAR_KING;
+            }
+
+            let shield = ranks_ahead(color, king) & file_mask(file) & own_pawns;
+            if let Some(pawn) = squares(shield).min_by_key(|pawn| pawn.abs_diff(king)) {
+                let distance = (pawn / 8).abs_diff(king / 8);
+                if let Some(bonus) = PAWN_SHIELD.get(distance - 1) {
+                    score += bonus;
+                }
+            }
+        }
+
+        let zone = king_attacks(king) | 1 << king;
+        for kind in [
+            PieceKind::Knight,
+            PieceKind::Bishop,
+            PieceKind::Rook,
+            PieceKind::Queen,
+        ] {
+            for square in squares(position.pieces(enemy, kind)) {
+                let hits = attacks_from(kind, enemy, square, occupied) & zone;
+                score += KING_ZONE_ATTACK * hits.count_ones() as i32;
+            }
+        }
+
+        breakdown.king_safety.add(color, score, 0);
+    }
+}
+
+pub fn evaluate_breakdown(state: &GameState) -> EvalBreakdown {
+    let position = Position::from_board(&state.board);
+    let mut breakdown = EvalBreakdown {
+        phase: 0,
+        material: TermScore::default(),
+        piece_squares: TermScore::default(),
+        mobility: TermScore::default(),
+        pawn_structure: TermScore::default(),
+        king_safety: TermScore::default(),
+        bishop_pair: TermScore::default(),
+        total: 0,
+    };
+
+    evaluate_pieces(&position, &mut breakdown);
+    evaluate_pawns(&position, &mut breakdown);
+    evaluate_king_safety(&position, &mut breakdown);
+
+    let white = breakdown.white_score();
+    breakdown.total = match state.active_color {
+        PieceColor::White => white,
+        PieceColor::Black => -white,
+    };
+    breakdown
+}
+
+// Centipawns from the side to move's point of view.
+pub fn evaluate(state: &GameState) -> i32 {
+    evaluate_breakdown(state).total
+}
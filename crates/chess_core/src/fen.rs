@@ -1,19 +1,291 @@
-use crate::game::{initial_state, EngineError, GameState};
+use thiserror::Error;
+
+use crate::board::{empty_board, Board, Piece, PieceColor, Square};
+use crate::game::{initial_state, CastlingRights, EngineError, GameState, GameStatus};
+use crate::rules::evaluate_status;
 
 pub const START_POSITION_FEN: &str = "rn1qkbnr/pppbpppp/8/3p4/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 pub const STANDARD_START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
+pub const PLACEMENT_FIELD: usize = 0;
+pub const ACTIVE_COLOR_FIELD: usize = 1;
+pub const CASTLING_FIELD: usize = 2;
+pub const EN_PASSANT_FIELD: usize = 3;
+pub const HALFMOVE_CLOCK_FIELD: usize = 4;
+pub const FULLMOVE_NUMBER_FIELD: usize = 5;
+
+// Ranks are reported as printed on the board (1-8), not as array indices.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum FenError {
+    #[error("expected 6 space-separated fields, found {found}")]
+    WrongFieldCount { found: usize },
+    #[error("expected 8 ranks in piece placement, found {found}")]
+    WrongRankCount { found: usize },
+    #[error("invalid piece character '{character}' on rank {rank}")]
+    InvalidPiece { rank: u8, character: char },
+    #[error("rank {rank} describes more than 8 squares")]
+    RankOverflow { rank: u8 },
+    #[error("rank {rank} describes fewer than 8 squares")]
+    RankUnderflow { rank: u8 },
+    #[error("invalid side to move '{value}'")]
+    InvalidSideToMove { value: String },
+    #[error("invalid castling character '{character}'")]
+    InvalidCastling { character: char },
+    #[error("invalid en passant square '{value}'")]
+    InvalidEnPassant { value: String },
+    #[error("{} must be a valid move counter, found '{value}'", clock_name(*field))]
+    InvalidClock { field: usize, value: String },
+}
+
+fn clock_name(field: usize) -> &'static str {
+    if field == HALFMOVE_CLOCK_FIELD {
+        "halfmove clock"
+    } else {
+        "fullmove number"
+    }
+}
+
+impl FenError {
+    pub fn field(&self) -> usize {
+        match self {
+            Self::WrongFieldCount { .. }
+            | Self::WrongRankCount { .. }
+            | Self::InvalidPiece { .. }
+            | Self::RankOverflow { .. }
+            | Self::RankUnderflow { .. } => PLACEMENT_FIELD,
+            Self::InvalidSideToMove { .. } => ACTIVE_COLOR_FIELD,
+            Self::InvalidCastling { .. } => CASTLING_FIELD,
+            Self::InvalidEnPassant { .. } => EN_PASSANT_FIELD,
+            Self::InvalidClock { field, .. } => *field,
+        }
+    }
+
+    pub fn character(&self) -> Option<char> {
+        match self {
+            Self::InvalidPiece { character, .. } | Self::InvalidCastling { character } => {
+                Some(*character)
+            }
+            _ => None,
+        }
+    }
+}
+
 pub fn from_fen(fen: &str) -> Result<GameState, EngineError> {
-    match fen.trim() {
-        "startpos" | STANDARD_START_FEN => Ok(initial_state()),
-        _ => Err(EngineError::UnsupportedFen),
+    let fen = fen.trim();
+    if fen == "startpos" {
+        return Ok(initial_state());
+    }
+
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    if fields.len() != 6 {
+        return Err(FenError::WrongFieldCount {
+            found: fields.len(),
+        }
+        .into());
+    }
+
+    let mut state = GameState {
+        board: parse_board(fields[PLACEMENT_FIELD])?,
+        active_color: parse_active_color(fields[ACTIVE_COLOR_FIELD])?,
+        status: GameStatus::InProgress,
+        halfmove_clock: parse_clock(fields[HALFMOVE_CLOCK_FIELD], HALFMOVE_CLOCK_FIELD)?,
+        fullmove_number: parse_clock(fields[FULLMOVE_NUMBER_FIELD], FULLMOVE_NUMBER_FIELD)?,
+        castling_rights: parse_castling_rights(fields[CASTLING_FIELD])?,
+        en_passant_target: parse_en_passant_target(fields[EN_PASSANT_FIELD])?,
+    };
+
+    if state.fullmove_number == 0 {
+        return Err(FenError::InvalidClock {
+            field: FULLMOVE_NUMBER_FIELD,
+            value: fields[FULLMOVE_NUMBER_FIELD].to_string(),
+        }
+        .into());
+    }
+
+    let errors = state.validate();
+    if !errors.is_empty() {
+        return Err(EngineError::IllegalPosition(errors));
+    }
+
+    state.status = evaluate_status(&state);
+    Ok(state)
+}
+
+fn parse_board(placement: &str) -> Result<Board, FenError> {
+    let ranks: Vec<&str> = placement.split('/').collect();
+    if ranks.len() != 8 {
+        return Err(FenError::WrongRankCount { found: ranks.len() });
     }
+
+    let mut board = empty_board();
+
+    // FEN lists ranks from the eighth down to the first.
+    for (index, rank_text) in ranks.iter().enumerate() {
+        let rank = 7 - index;
+        let rank_number = rank as u8 + 1;
+        let mut file = 0usize;
+
+        for c in rank_text.chars() {
+            match c.to_digit(10) {
+                Some(empty @ 1..=8) => file += empty as usize,
+                Some(_) => {
+                    return Err(FenError::InvalidPiece {
+                        rank: rank_number,
+                        character: c,
+                    })
+                }
+                None => {
+                    let piece = Piece::from_fen_char(c).ok_or(FenError::InvalidPiece {
+                        rank: rank_number,
+                        character: c,
+                    })?;
+                    if file < 8 {
+                        board[rank][file] = Some(piece);
+                    }
+                    file += 1;
+                }
+            }
+
+            if file > 8 {
+                return Err(FenError::RankOverflow { rank: rank_number });
+            }
+        }
+
+        if file != 8 {
+            return Err(FenError::RankUnderflow { rank: rank_number });
+        }
+    }
+
+    Ok(board)
+}
+
+fn parse_active_color(field: &str) -> Result<PieceColor, FenError> {
+    match field {
+        "w" => Ok(PieceColor::White),
+        "b" => Ok(PieceColor::Black),
+        _ => Err(FenError::InvalidSideToMove {
+            value: field.to_string(),
+        }),
+    }
+}
+
+fn parse_castling_rights(field: &str) -> Result<CastlingRights, FenError> {
+    let mut rights = CastlingRights {
+        white_king_side: false,
+        white_queen_side: false,
+        black_king_side: false,
+        black_queen_side: false,
+    };
+
+    if field == "-" {
+        return Ok(rights);
+    }
+
+    for c in field.chars() {
+        let flag = match c {
+            'K' => &mut rights.white_king_side,
+            'Q' => &mut rights.white_queen_side,
+            'k' => &mut rights.black_king_side,
+            'q' => &mut rights.black_queen_side,
+            _ => return Err(FenError::InvalidCastling { character: c }),
+        };
+
+        if *flag {
+            return Err(FenError::InvalidCastling { character: c });
+        }
+        *flag = true;
+    }
+
+    Ok(rights)
+}
+
+fn parse_en_passant_target(field: &str) -> Result<Option<Square>, FenError> {
+    if field == "-" {
+        return Ok(None);
+    }
+
+    match Square::from_algebraic(field) {
+        Some(square) if square.rank == 2 || square.rank == 5 => Ok(Some(square)),
+        _ => Err(FenError::InvalidEnPassant {
+            value: field.to_string(),
+        }),
+    }
+}
+
+fn parse_clock(value: &str, field: usize) -> Result<u32, FenError> {
+    value.parse().map_err(|_| FenError::InvalidClock {
+        field,
+        value: value.to_string(),
+    })
 }
 
 pub fn to_fen(state: &GameState) -> String {
-    if state == &initial_state() {
-        STANDARD_START_FEN.to_string()
+    let en_passant = state
+        .en_passant_target
+        .map(Square::to_algebraic)
+        .unwrap_or_else(|| "-".to_string());
+
+    format!(
+        "{} {} {} {} {} {}",
+        board_to_fen(&state.board),
+        match state.active_color {
+            PieceColor::White => "w",
+            PieceColor::Black => "b",
+        },
+        castling_rights_to_fen(state.castling_rights),
+        en_passant,
+        state.halfmove_clock,
+        state.fullmove_number
+    )
+}
+
+fn board_to_fen(board: &Board) -> String {
+    let mut placement = String::new();
+
+    for rank in (0..8).rev() {
+        let mut empty = 0;
+        for square in board[rank] {
+            match square {
+                Some(piece) => {
+                    if empty > 0 {
+                        placement.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    placement.push(piece.to_fen_char());
+                }
+                None => empty += 1,
+            }
+        }
+
+        if empty > 0 {
+            placement.push_str(&empty.to_string());
+        }
+        if rank > 0 {
+            placement.push('/');
+        }
+    }
+
+    placement
+}
+
+fn castling_rights_to_fen(rights: CastlingRights) -> String {
+    let mut field = String::new();
+    if rights.white_king_side {
+        field.push('K');
+    }
+    if rights.white_queen_side {
+        field.push('Q');
+    }
+    if rights.black_king_side {
+        field.push('k');
+    }
+    if rights.black_queen_side {
+        field.push('q');
+    }
+
+    if field.is_empty() {
+        "-".to_string()
     } else {
-        "unsupported-fen-serialization".to_string()
+        field
     }
 }
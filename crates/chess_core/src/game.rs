@@ -2,8 +2,13 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::board::{standard_start_board, Board, Piece, PieceColor, PieceKind, Square};
-use crate::move_gen::generate_legal_moves_for_square;
+use crate::fen::FenError;
+use crate::move_gen::{
+    castling_rook_squares, generate_legal_moves_for_square, is_castling, is_in_check_for_color,
+    make_move, opposite, MoveUndo,
+};
 use crate::rules::evaluate_status;
+use crate::validation::{validate_position, PositionError};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Move {
@@ -38,11 +43,78 @@ impl CastlingRights {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DrawReason {
+    Agreement,
+    ThreefoldRepetition,
+    FivefoldRepetition,
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    InsufficientMaterial,
+    TimeoutVsInsufficientMaterial,
+}
+
+// `Checkmate` holds the winner; the other variants hold the side that can no longer play.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameStatus {
     InProgress,
     Checkmate(PieceColor),
     Stalemate(PieceColor),
+    Draw(DrawReason),
+    Resigned(PieceColor),
+    Timeout(PieceColor),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Termination {
+    Checkmate,
+    Stalemate,
+    Resignation,
+    DrawAgreement,
+    ThreefoldRepetition,
+    FivefoldRepetition,
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    InsufficientMaterial,
+    Timeout,
+    TimeoutVsInsufficientMaterial,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameResult {
+    pub winner: Option<PieceColor>,
+    pub termination: Termination,
+}
+
+impl GameStatus {
+    pub fn result(&self) -> Option<GameResult> {
+        let (winner, termination) = match self {
+            Self::InProgress => return None,
+            Self::Checkmate(winner) => (Some(*winner), Termination::Checkmate),
+            Self::Stalemate(_) => (None, Termination::Stalemate),
+            Self::Resigned(loser) => (Some(opposite(*loser)), Termination::Resignation),
+            Self::Timeout(loser) => (Some(opposite(*loser)), Termination::Timeout),
+            Self::Draw(reason) => (
+                None,
+                match reason {
+                    DrawReason::Agreement => Termination::DrawAgreement,
+                    DrawReason::ThreefoldRepetition => Termination::ThreefoldRepetition,
+                    DrawReason::FivefoldRepetition => Termination::FivefoldRepetition,
+                    DrawReason::FiftyMoveRule => Termination::FiftyMoveRule,
+                    DrawReason::SeventyFiveMoveRule => Termination::SeventyFiveMoveRule,
+                    DrawReason::InsufficientMaterial => Termination::InsufficientMaterial,
+                    DrawReason::TimeoutVsInsufficientMaterial => {
+                        Termination::TimeoutVsInsufficientMaterial
+                    }
+                },
+            ),
+        };
+
+        Some(GameResult {
+            winner,
+            termination,
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -56,10 +128,40 @@ pub struct GameState {
     pub en_passant_target: Option<Square>,
 }
 
+impl GameState {
+    pub fn validate(&self) -> Vec<PositionError> {
+        validate_position(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveFlags {
+    pub capture: bool,
+    pub en_passant: bool,
+    pub castling: bool,
+    pub double_push: bool,
+    pub promotion: bool,
+}
+
+// What a move did, so a UI can animate and play sounds without re-deriving it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveDetails {
+    pub moved_piece: Piece,
+    pub captured_piece: Option<Piece>,
+    // Differs from the destination square for en passant.
+    pub captured_square: Option<Square>,
+    pub castle_rook_from: Option<Square>,
+    pub castle_rook_to: Option<Square>,
+    pub gives_check: bool,
+    pub gives_mate: bool,
+    pub flags: MoveFlags,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MoveResult {
     pub state: GameState,
     pub move_applied: Move,
+    pub details: MoveDetails,
 }
 
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
@@ -72,8 +174,28 @@ pub enum EngineError {
     NotActivePlayersPiece,
     #[error("illegal move")]
     IllegalMove,
-    #[error("unsupported fen")]
-    UnsupportedFen,
+    #[error("game is already over")]
+    GameOver,
+    #[error("no pending draw offer from the opponent")]
+    NoDrawOffer,
+    #[error("no draw can be claimed in this position")]
+    InvalidDrawClaim,
+    #[error("ply {0} is outside the game history")]
+    PlyOutOfRange(usize),
+    #[error("invalid uci move '{0}'")]
+    InvalidUci(String),
+    #[error("invalid fen: {0}")]
+    InvalidFen(#[from] FenError),
+    #[error("illegal position: {}", format_position_errors(.0))]
+    IllegalPosition(Vec<PositionError>),
+}
+
+fn format_position_errors(errors: &[PositionError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 pub fn initial_state() -> GameState {
@@ -117,7 +239,7 @@ fn normalize_promotion(
     Ok(Some(promotion))
 }
 
-fn update_castling_rights(
+pub(crate) fn update_castling_rights(
     current: CastlingRights,
     moved_piece: Piece,
     from: Square,
@@ -163,8 +285,16 @@ fn update_castling_rights(
 }
 
 pub fn apply_move_to_state(state: &GameState, input: MoveInput) -> Result<MoveResult, EngineError> {
+    apply_move_with_undo(state, input).map(|(result, _)| result)
+}
+
+// Also returns the undo record, for callers that update a hash incrementally.
+pub(crate) fn apply_move_with_undo(
+    state: &GameState,
+    input: MoveInput,
+) -> Result<(MoveResult, MoveUndo), EngineError> {
     if !matches!(state.status, GameStatus::InProgress) {
-        return Err(EngineError::IllegalMove);
+        return Err(EngineError::GameOver);
     }
 
     let mut next = state.clone();
@@ -189,109 +319,33 @@ pub fn apply_move_to_state(state: &GameState, input: MoveInput) -> Result<MoveRe
         return Err(EngineError::IllegalMove);
     }
 
-    let target_piece = next.board[input.to.rank as usize][input.to.file as usize];
-    let is_en_passant_capture = piece.kind == PieceKind::Pawn
-        && input.from.file != input.to.file
-        && target_piece.is_none()
-        && next.en_passant_target == Some(input.to);
-
-    let is_capture = target_piece.is_some() || is_en_passant_capture;
-
-    next.board[input.from.rank as usize][input.from.file as usize] = None;
-
-    if is_en_passant_capture {
-        let capture_rank = if piece.color == PieceColor::White {
-            input.to.rank as i16 - 1
-        } else {
-            input.to.rank as i16 + 1
-        };
-
-        if (0..8).contains(&capture_rank) {
-            next.board[capture_rank as usize][input.to.file as usize] = None;
-        }
-    }
-
-    if piece.kind == PieceKind::King && (input.from.file as i16 - input.to.file as i16).abs() == 2 {
-        let rook_rank = input.from.rank;
-        if input.to.file > input.from.file {
-            // King-side castling.
-            let rook_from = Square {
-                file: 7,
-                rank: rook_rank,
-            };
-            let rook_to = Square {
-                file: 5,
-                rank: rook_rank,
-            };
-            let rook = next.board[rook_from.rank as usize][rook_from.file as usize];
-            next.board[rook_from.rank as usize][rook_from.file as usize] = None;
-            next.board[rook_to.rank as usize][rook_to.file as usize] = rook;
-        } else {
-            // Queen-side castling.
-            let rook_from = Square {
-                file: 0,
-                rank: rook_rank,
-            };
-            let rook_to = Square {
-                file: 3,
-                rank: rook_rank,
-            };
-            let rook = next.board[rook_from.rank as usize][rook_from.file as usize];
-            next.board[rook_from.rank as usize][rook_from.file as usize] = None;
-            next.board[rook_to.rank as usize][rook_to.file as usize] = rook;
-        }
-    }
-
-    let moved_piece = if let Some(promoted_kind) = requested_move.promotion {
-        Piece {
-            color: piece.color,
-            kind: promoted_kind,
-        }
-    } else {
-        piece
-    };
-
-    next.board[input.to.rank as usize][input.to.file as usize] = Some(moved_piece);
-
-    next.castling_rights = update_castling_rights(
-        next.castling_rights,
-        piece,
-        input.from,
-        input.to,
-        is_capture,
-    );
-
-    next.en_passant_target = if piece.kind == PieceKind::Pawn
-        && (input.to.rank as i16 - input.from.rank as i16).abs() == 2
-    {
-        Some(Square {
-            file: input.from.file,
-            rank: ((input.from.rank as u16 + input.to.rank as u16) / 2) as u8,
-        })
-    } else {
-        None
-    };
+    let undo = make_move(&mut next, &requested_move).ok_or(EngineError::NoPieceAtSource)?;
+    next.status = evaluate_status(&next);
 
-    let moved_color = next.active_color;
-    next.active_color = match next.active_color {
-        PieceColor::White => PieceColor::Black,
-        PieceColor::Black => PieceColor::White,
+    let castle_rook =
+        is_castling(piece, &requested_move).then(|| castling_rook_squares(&requested_move));
+    let details = MoveDetails {
+        moved_piece: piece,
+        captured_piece: undo.captured.map(|(_, captured)| captured),
+        captured_square: undo.captured.map(|(square, _)| square),
+        castle_rook_from: castle_rook.map(|(from, _)| from),
+        castle_rook_to: castle_rook.map(|(_, to)| to),
+        gives_check: is_in_check_for_color(&next, next.active_color),
+        gives_mate: matches!(next.status, GameStatus::Checkmate(_)),
+        flags: MoveFlags {
+            capture: undo.captured.is_some(),
+            en_passant: undo.captured.is_some_and(|(square, _)| square != input.to),
+            castling: castle_rook.is_some(),
+            double_push: piece.kind == PieceKind::Pawn
+                && input.from.rank.abs_diff(input.to.rank) == 2,
+            promotion: requested_move.promotion.is_some(),
+        },
     };
 
-    if moved_color == PieceColor::Black {
-        next.fullmove_number += 1;
-    }
-
-    if piece.kind == PieceKind::Pawn || is_capture {
-        next.halfmove_clock = 0;
-    } else {
-        next.halfmove_clock += 1;
-    }
-
-    next.status = evaluate_status(&next);
-
-    Ok(MoveResult {
+    let result = MoveResult {
         state: next,
         move_applied: requested_move,
-    })
+        details,
+    };
+    Ok((result, undo))
 }
@@ -1,47 +1,292 @@
+pub mod bitboard;
 pub mod board;
+pub mod clock;
+pub mod eval;
 pub mod fen;
 pub mod game;
 pub mod move_gen;
+pub mod perft;
+pub mod pgn;
+pub mod repetition;
 pub mod rules;
+pub mod san;
+pub mod search;
+pub mod transposition;
+pub mod uci;
+pub mod validation;
+pub mod zobrist;
 
 pub use board::{Piece, PieceColor, PieceKind, Square};
-pub use game::{CastlingRights, EngineError, GameState, GameStatus, Move, MoveInput, MoveResult};
+pub use clock::{ClockError, GameClock, TimeBonus, TimeControl, TimeControlStage};
+pub use eval::{EvalBreakdown, TermScore};
+pub use fen::FenError;
+pub use game::{
+    CastlingRights, DrawReason, EngineError, GameResult, GameState, GameStatus, Move, MoveDetails,
+    MoveFlags, MoveInput, MoveResult, Termination,
+};
+pub use move_gen::{MoveList, MoveUndo};
+pub use perft::{perft, perft_divide};
+pub use pgn::{PgnError, PgnGame, PgnResult, PgnTags};
+pub use san::SanError;
+pub use search::{SearchLimits, SearchResult};
+pub use transposition::TranspositionTable;
+pub use validation::PositionError;
+pub use zobrist::ZobristHash;
+
+use move_gen::opposite;
 
 #[derive(Debug, Clone)]
 pub struct ChessEngine {
-    state: GameState,
+    initial_state: GameState,
+    history: Vec<MoveResult>,
+    // `hashes[ply]` is the Zobrist hash of `state_at(ply)`.
+    hashes: Vec<ZobristHash>,
+    ply: usize,
+    draw_offer: Option<PieceColor>,
+    // The last position once the game is ended by resignation, timeout or
+    // agreement, which are not moves and so have no entry in `history`.
+    terminal: Option<GameState>,
+    // PGN tags for export; `Result`, `SetUp` and `FEN` are always derived.
+    tags: PgnTags,
 }
 
 impl ChessEngine {
     pub fn new_game() -> Self {
-        Self { state: new_game() }
+        Self::from_state(new_game())
     }
 
     pub fn from_fen(fen: &str) -> Result<Self, EngineError> {
         let state = from_fen(fen)?;
-        Ok(Self { state })
+        Ok(Self::from_state(state))
+    }
+
+    fn from_state(state: GameState) -> Self {
+        Self {
+            hashes: vec![zobrist::hash(&state)],
+            initial_state: state,
+            history: Vec::new(),
+            ply: 0,
+            draw_offer: None,
+            terminal: None,
+            tags: Vec::new(),
+        }
     }
 
     pub fn legal_moves(&self, square: Square) -> Vec<Move> {
-        legal_moves(&self.state, square)
+        legal_moves(self.state(), square)
     }
 
+    pub fn legal_moves_all(&self) -> MoveList {
+        legal_moves_all(self.state())
+    }
+
+    // Playing a move after an undo discards the redo branch.
     pub fn apply_move(&mut self, input: MoveInput) -> Result<MoveResult, EngineError> {
-        let result = apply_move(&self.state, input)?;
-        self.state = result.state.clone();
+        let mover = self.state().active_color;
+        let (mut result, undo) = game::apply_move_with_undo(self.state(), input)?;
+        let hash = zobrist::hash_after_move(self.zobrist_hash(), &result.state, &undo);
+        // Moving instead of accepting declines the opponent's offer.
+        if self.draw_offer != Some(mover) {
+            self.draw_offer = None;
+        }
+        self.history.truncate(self.ply);
+        self.history.push(result.clone());
+        self.hashes.truncate(self.ply + 1);
+        self.hashes.push(hash);
+        self.ply += 1;
+        self.terminal = None;
+
+        if result.state.status == GameStatus::InProgress
+            && self.repetition_count() >= repetition::FIVEFOLD
+        {
+            result.state.status = GameStatus::Draw(DrawReason::FivefoldRepetition);
+            self.history[self.ply - 1].state.status = result.state.status.clone();
+        }
+
         Ok(result)
     }
 
+    pub fn undo(&mut self) -> Option<&MoveResult> {
+        if self.ply == 0 {
+            return None;
+        }
+        self.ply -= 1;
+        self.history.get(self.ply)
+    }
+
+    pub fn redo(&mut self) -> Option<&MoveResult> {
+        if self.ply == self.history.len() {
+            return None;
+        }
+        self.ply += 1;
+        self.history.get(self.ply - 1)
+    }
+
+    pub fn goto_ply(&mut self, ply: usize) -> Result<(), EngineError> {
+        if ply > self.history.len() {
+            return Err(EngineError::PlyOutOfRange(ply));
+        }
+        self.ply = ply;
+        Ok(())
+    }
+
+    // Includes plies after the current one that are still available to redo.
+    pub fn history(&self) -> &[MoveResult] {
+        &self.history
+    }
+
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    pub fn state_at(&self, ply: usize) -> Option<&GameState> {
+        match ply {
+            0 => Some(&self.initial_state),
+            _ => self.history.get(ply - 1).map(|result| &result.state),
+        }
+    }
+
+    // Polyglot-compatible key of the current position, kept up to date move by move.
+    pub fn zobrist_hash(&self) -> ZobristHash {
+        self.hashes[self.ply]
+    }
+
     pub fn status(&self) -> GameStatus {
-        status(&self.state)
+        self.state().status.clone()
+    }
+
+    // How many times the current position has occurred, including now.
+    pub fn repetition_count(&self) -> usize {
+        repetition::repetition_count((0..=self.ply).filter_map(|ply| self.state_at(ply)))
+    }
+
+    pub fn can_claim_threefold_repetition(&self) -> bool {
+        self.status() == GameStatus::InProgress && self.repetition_count() >= repetition::THREEFOLD
+    }
+
+    pub fn can_claim_fifty_move_rule(&self) -> bool {
+        rules::can_claim_fifty_move_rule(self.state())
+    }
+
+    pub fn result(&self) -> Option<GameResult> {
+        self.state().status.result()
+    }
+
+    pub fn resign(&mut self, color: PieceColor) -> Result<GameResult, EngineError> {
+        self.finish(GameStatus::Resigned(color))
+    }
+
+    // Records a flag fall reported by a `GameClock` or an external clock.
+    pub fn timeout(&mut self, color: PieceColor) -> Result<GameResult, EngineError> {
+        self.finish(clock::timeout_status(self.state(), color))
+    }
+
+    pub fn offer_draw(&mut self, color: PieceColor) -> Result<(), EngineError> {
+        if self.status() != GameStatus::InProgress {
+            return Err(EngineError::GameOver);
+        }
+        self.draw_offer = Some(color);
+        Ok(())
+    }
+
+    pub fn draw_offer(&self) -> Option<PieceColor> {
+        self.draw_offer
+    }
+
+    pub fn decline_draw(&mut self, color: PieceColor) -> Result<(), EngineError> {
+        if self.draw_offer != Some(opposite(color)) {
+            return Err(EngineError::NoDrawOffer);
+        }
+        self.draw_offer = None;
+        Ok(())
+    }
+
+    pub fn accept_draw(&mut self, color: PieceColor) -> Result<GameResult, EngineError> {
+        if self.draw_offer != Some(opposite(color)) {
+            return Err(EngineError::NoDrawOffer);
+        }
+        self.finish(GameStatus::Draw(DrawReason::Agreement))
+    }
+
+    // Threefold repetition is preferred when both claims are valid.
+    pub fn claim_draw(&mut self) -> Result<GameResult, EngineError> {
+        if self.can_claim_threefold_repetition() {
+            self.finish(GameStatus::Draw(DrawReason::ThreefoldRepetition))
+        } else if self.can_claim_fifty_move_rule() {
+            self.finish(GameStatus::Draw(DrawReason::FiftyMoveRule))
+        } else if self.status() != GameStatus::InProgress {
+            Err(EngineError::GameOver)
+        } else {
+            Err(EngineError::InvalidDrawClaim)
+        }
+    }
+
+    // Ends the game at the current ply; any redo branch is discarded.
+    fn finish(&mut self, status: GameStatus) -> Result<GameResult, EngineError> {
+        if self.status() != GameStatus::InProgress {
+            return Err(EngineError::GameOver);
+        }
+
+        let mut state = self.state().clone();
+        state.status = status;
+        self.history.truncate(self.ply);
+        self.hashes.truncate(self.ply + 1);
+        self.draw_offer = None;
+        let result = state.status.result().ok_or(EngineError::GameOver);
+        self.terminal = Some(state);
+        result
+    }
+
+    // Searches the current position; earlier positions count towards repetition.
+    // `table` is best kept for the whole game so each search builds on the last.
+    pub fn search(
+        &self,
+        limits: SearchLimits,
+        table: &mut TranspositionTable,
+        now_ms: impl FnMut() -> u64,
+    ) -> SearchResult {
+        let history = &self.hashes[..self.ply];
+        search::search_with_table(self.state(), history, limits, table, now_ms)
     }
 
     pub fn to_fen(&self) -> String {
-        to_fen(&self.state)
+        to_fen(self.state())
     }
 
     pub fn state(&self) -> &GameState {
-        &self.state
+        match &self.terminal {
+            Some(state) if self.ply == self.history.len() => state,
+            _ => self.state_at(self.ply).unwrap_or(&self.initial_state),
+        }
+    }
+
+    // Replaces any earlier value for the same tag.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(key, _)| key == name) {
+            Some((_, existing)) => *existing = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn tags(&self) -> &PgnTags {
+        &self.tags
+    }
+
+    pub fn pgn_game(&self) -> PgnGame {
+        PgnGame {
+            tags: self.tags.clone(),
+            initial_state: self.initial_state.clone(),
+            moves: self.history[..self.ply]
+                .iter()
+                .map(|result| result.move_applied.clone())
+                .collect(),
+            state: self.state().clone(),
+            result: PgnResult::from_status(&self.state().status),
+        }
+    }
+
+    pub fn to_pgn(&self) -> String {
+        self.pgn_game().to_pgn()
     }
 }
 
@@ -57,12 +302,32 @@ pub fn legal_moves(state: &GameState, square: Square) -> Vec<Move> {
     move_gen::generate_legal_moves_for_square(state, square)
 }
 
+pub fn legal_moves_all(state: &GameState) -> MoveList {
+    move_gen::generate_legal_moves(state)
+}
+
+pub fn legal_captures(state: &GameState) -> MoveList {
+    move_gen::generate_legal_captures(state)
+}
+
+pub fn legal_checks(state: &GameState) -> MoveList {
+    move_gen::generate_legal_checks(state)
+}
+
+pub fn legal_quiet_moves(state: &GameState) -> MoveList {
+    move_gen::generate_legal_quiet_moves(state)
+}
+
 pub fn apply_move(state: &GameState, input: MoveInput) -> Result<MoveResult, EngineError> {
-    let result = game::apply_move_to_state(state, input)?;
-    Ok(MoveResult {
-        state: result.state,
-        move_applied: result.move_applied,
-    })
+    game::apply_move_to_state(state, input)
+}
+
+pub fn evaluate(state: &GameState) -> EvalBreakdown {
+    eval::evaluate_breakdown(state)
+}
+
+pub fn zobrist_hash(state: &GameState) -> ZobristHash {
+    zobrist::hash(state)
 }
 
 pub fn status(state: &GameState) -> GameStatus {
@@ -84,3 +349,79 @@ mod checkmate_test;
 #[cfg(test)]
 #[path = "tests/stalemate.rs"]
 mod stalemate_test;
+
+#[cfg(test)]
+#[path = "tests/fen.rs"]
+mod fen_test;
+
+#[cfg(test)]
+#[path = "tests/validation.rs"]
+mod validation_test;
+
+#[cfg(test)]
+#[path = "tests/san.rs"]
+mod san_test;
+
+#[cfg(test)]
+#[path = "tests/uci.rs"]
+mod uci_test;
+
+#[cfg(test)]
+#[path = "tests/pgn.rs"]
+mod pgn_test;
+
+#[cfg(test)]
+#[path = "tests/history.rs"]
+mod history_test;
+
+#[cfg(test)]
+#[path = "tests/repetition.rs"]
+mod repetition_test;
+
+#[cfg(test)]
+#[path = "tests/draw_rules.rs"]
+mod draw_rules_test;
+
+#[cfg(test)]
+#[path = "tests/termination.rs"]
+mod termination_test;
+
+#[cfg(test)]
+#[path = "tests/clock.rs"]
+mod clock_test;
+
+#[cfg(test)]
+#[path = "tests/perft.rs"]
+mod perft_test;
+
+#[cfg(test)]
+#[path = "tests/bitboard.rs"]
+mod bitboard_test;
+
+#[cfg(test)]
+#[path = "tests/make_move.rs"]
+mod make_move_test;
+
+#[cfg(test)]
+#[path = "tests/zobrist.rs"]
+mod zobrist_test;
+
+#[cfg(test)]
+#[path = "tests/move_execution.rs"]
+mod move_execution_test;
+
+#[cfg(test)]
+#[path = "tests/move_details.rs"]
+mod move_details_test;
+
+#[cfg(test)]
+#[path = "tests/search.rs"]
+mod search_test;
+
+#[cfg(test)]
+#[path = "tests/eval.rs"]
+mod eval_test;
+
+#[cfg(test)]
+#[path = "tests/transposition.rs"]
+mod transposition_test;
@@ -1,33 +1,16 @@
-use crate::board::{Piece, PieceColor, PieceKind, Square};
-use crate::game::{GameState, Move};
+use crate::bitboard::{
+    bishop_attacks, index_square, king_attacks, knight_attacks, pawn_attacks, queen_attacks,
+    rook_attacks, square_bit, square_index, squares, Bitboard, Position, RANK_1, RANK_8,
+};
+use crate::board::{Board, Piece, PieceColor, PieceKind, Square};
+use crate::game::{update_castling_rights, CastlingRights, GameState, Move};
 
-fn is_within_bounds(file: i16, rank: i16) -> bool {
-    (0..8).contains(&file) && (0..8).contains(&rank)
-}
-
-fn square_from_coords(file: i16, rank: i16) -> Option<Square> {
-    if is_within_bounds(file, rank) {
-        Some(Square {
-            file: file as u8,
-            rank: rank as u8,
-        })
-    } else {
-        None
-    }
-}
+pub type MoveList = Vec<Move>;
 
-fn piece_at(state: &GameState, square: Square) -> Option<Piece> {
+pub(crate) fn piece_at(state: &GameState, square: Square) -> Option<Piece> {
     state.board[square.rank as usize][square.file as usize]
 }
 
-fn is_empty(state: &GameState, square: Square) -> bool {
-    piece_at(state, square).is_none()
-}
-
-fn is_enemy_piece(state: &GameState, square: Square, color: PieceColor) -> bool {
-    piece_at(state, square).is_some_and(|piece| piece.color != color)
-}
-
 fn promotion_pieces() -> [PieceKind; 4] {
     [
         PieceKind::Queen,
@@ -37,61 +20,23 @@ fn promotion_pieces() -> [PieceKind; 4] {
     ]
 }
 
-fn push_move_with_optional_promotion(
-    moves: &mut Vec<Move>,
-    from: Square,
-    to: Square,
-    kind: PieceKind,
-) {
-    if kind == PieceKind::Pawn && (to.rank == 0 || to.rank == 7) {
-        for promotion in promotion_pieces() {
-            moves.push(Move {
-                from,
-                to,
-                promotion: Some(promotion),
-            });
-        }
-    } else {
-        moves.push(Move {
-            from,
-            to,
-            promotion: None,
-        });
-    }
-}
-
-fn generate_sliding_moves(
-    state: &GameState,
-    from: Square,
-    color: PieceColor,
-    directions: &[(i16, i16)],
-    moves: &mut Vec<Move>,
-) {
-    for (df, dr) in directions {
-        let mut file = from.file as i16 + df;
-        let mut rank = from.rank as i16 + dr;
-
-        while let Some(to) = square_from_coords(file, rank) {
-            let target = piece_at(state, to);
-            if let Some(target_piece) = target {
-                if target_piece.color != color {
-                    moves.push(Move {
-                        from,
-                        to,
-                        promotion: None,
-                    });
-                }
-                break;
+fn push_moves(moves: &mut MoveList, from: usize, targets: Bitboard, promotes: bool) {
+    for to in squares(targets) {
+        let (from, to) = (index_square(from), index_square(to));
+        if promotes && (RANK_1 | RANK_8) & square_bit(to) != 0 {
+            for promotion in promotion_pieces() {
+                moves.push(Move {
+                    from,
+                    to,
+                    promotion: Some(promotion),
+                });
             }
-
+        } else {
             moves.push(Move {
                 from,
                 to,
                 promotion: None,
             });
-
-            file += df;
-            rank += dr;
         }
     }
 }
@@ -116,496 +61,423 @@ fn castling_rights_for_color(state: &GameState, color: PieceColor) -> (bool, boo
     }
 }
 
-fn add_castling_moves(state: &GameState, from: Square, color: PieceColor, moves: &mut Vec<Move>) {
-    if from.file != 4 || from.rank != king_home_rank(color) {
+fn add_castling_moves(
+    state: &GameState,
+    position: &Position,
+    from: usize,
+    color: PieceColor,
+    moves: &mut MoveList,
+) {
+    let home = king_home_rank(color) as usize * 8;
+    if from != home + 4 {
         return;
     }
 
     let enemy = opposite(color);
-    if is_square_attacked_by(state, from, enemy) {
+    if position.is_attacked_by(from, enemy) {
         return;
     }
 
     let (king_side_allowed, queen_side_allowed) = castling_rights_for_color(state, color);
-
-    if king_side_allowed {
-        let f = Square {
-            file: 5,
-            rank: from.rank,
-        };
-        let g = Square {
-            file: 6,
-            rank: from.rank,
-        };
-        let rook_square = Square {
-            file: 7,
-            rank: from.rank,
-        };
-
-        let rook_ok = piece_at(state, rook_square)
-            .is_some_and(|piece| piece.color == color && piece.kind == PieceKind::Rook);
-
-        if rook_ok
-            && is_empty(state, f)
-            && is_empty(state, g)
-            && !is_square_attacked_by(state, f, enemy)
-            && !is_square_attacked_by(state, g, enemy)
-        {
-            moves.push(Move {
-                from,
-                to: g,
-                promotion: None,
-            });
+    let occupied = position.occupied();
+    let rooks = position.pieces(color, PieceKind::Rook);
+    let sides = [
+        // (allowed, rook file, files that must be empty, files the king crosses, king target)
+        (king_side_allowed, 7, &[5, 6][..], &[5, 6][..], 6),
+        (queen_side_allowed, 0, &[1, 2, 3][..], &[3, 2][..], 2),
+    ];
+
+    for (allowed, rook_file, empty, crossed, target) in sides {
+        let can_castle = allowed
+            && rooks & (1 << (home + rook_file)) != 0
+            && empty
+                .iter()
+                .all(|file| occupied & (1 << (home + file)) == 0)
+            && crossed
+                .iter()
+                .all(|file| !position.is_attacked_by(home + file, enemy));
+
+        if can_castle {
+            push_moves(moves, from, 1 << (home + target), false);
         }
     }
+}
 
-    if queen_side_allowed {
-        let b = Square {
-            file: 1,
-            rank: from.rank,
-        };
-        let c = Square {
-            file: 2,
-            rank: from.rank,
-        };
-        let d = Square {
-            file: 3,
-            rank: from.rank,
-        };
-        let rook_square = Square {
-            file: 0,
-            rank: from.rank,
+fn pawn_targets(
+    state: &GameState,
+    position: &Position,
+    from: usize,
+    color: PieceColor,
+) -> Bitboard {
+    let empty = !position.occupied();
+    let bit: Bitboard = 1 << from;
+    let (single, double_rank) = match color {
+        PieceColor::White => (bit << 8, 3),
+        PieceColor::Black => (bit >> 8, 4),
+    };
+
+    let mut targets = single & empty;
+    if targets != 0 {
+        let double = match color {
+            PieceColor::White => targets << 8,
+            PieceColor::Black => targets >> 8,
         };
+        targets |= double & empty & (RANK_1 << (8 * double_rank));
+    }
 
-        let rook_ok = piece_at(state, rook_square)
-            .is_some_and(|piece| piece.color == color && piece.kind == PieceKind::Rook);
+    let attacks = pawn_attacks(color, from);
+    targets |= attacks & position.color(opposite(color));
 
-        if rook_ok
-            && is_empty(state, b)
-            && is_empty(state, c)
-            && is_empty(state, d)
-            && !is_square_attacked_by(state, d, enemy)
-            && !is_square_attacked_by(state, c, enemy)
+    // En passant needs the double-pushed enemy pawn to be behind the target square.
+    if let Some(target) = state.en_passant_target {
+        let target_bit = square_bit(target);
+        let captured = match color {
+            PieceColor::White => target_bit >> 8,
+            PieceColor::Black => target_bit << 8,
+        };
+        if attacks & target_bit != 0
+            && position.pieces(opposite(color), PieceKind::Pawn) & captured != 0
         {
-            moves.push(Move {
-                from,
-                to: c,
-                promotion: None,
-            });
+            targets |= target_bit;
         }
     }
+
+    targets
 }
 
-pub fn generate_pseudo_legal_moves_for_square(state: &GameState, square: Square) -> Vec<Move> {
-    let Some(piece) = piece_at(state, square) else {
-        return Vec::new();
+fn pseudo_legal_moves_from(
+    state: &GameState,
+    position: &Position,
+    from: usize,
+    moves: &mut MoveList,
+) {
+    let Some(piece) = position.piece_at(from) else {
+        return;
     };
 
-    let mut moves = Vec::new();
-
-    match piece.kind {
-        PieceKind::Pawn => {
-            let direction = if piece.color == PieceColor::White {
-                1
-            } else {
-                -1
-            };
-            let start_rank = if piece.color == PieceColor::White {
-                1
-            } else {
-                6
-            };
+    let own = position.color(piece.color);
+    let occupied = position.occupied();
 
-            if let Some(one_step) =
-                square_from_coords(square.file as i16, square.rank as i16 + direction)
-            {
-                if is_empty(state, one_step) {
-                    push_move_with_optional_promotion(&mut moves, square, one_step, piece.kind);
-
-                    if square.rank == start_rank {
-                        if let Some(two_step) = square_from_coords(
-                            square.file as i16,
-                            square.rank as i16 + (2 * direction),
-                        ) {
-                            if is_empty(state, two_step) {
-                                moves.push(Move {
-                                    from: square,
-                                    to: two_step,
-                                    promotion: None,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
+    let targets = match piece.kind {
+        PieceKind::Pawn => pawn_targets(state, position, from, piece.color),
+        PieceKind::Knight => knight_attacks(from) & !own,
+        PieceKind::Bishop => bishop_attacks(from, occupied) & !own,
+        PieceKind::Rook => rook_attacks(from, occupied) & !own,
+        PieceKind::Queen => queen_attacks(from, occupied) & !own,
+        PieceKind::King => king_attacks(from) & !own,
+    };
 
-            for df in [-1, 1] {
-                if let Some(capture_square) =
-                    square_from_coords(square.file as i16 + df, square.rank as i16 + direction)
-                {
-                    if is_enemy_piece(state, capture_square, piece.color) {
-                        push_move_with_optional_promotion(
-                            &mut moves,
-                            square,
-                            capture_square,
-                            piece.kind,
-                        );
-                    }
-                }
-            }
+    push_moves(moves, from, targets, piece.kind == PieceKind::Pawn);
 
-            if let Some(en_passant_target) = state.en_passant_target {
-                for df in [-1, 1] {
-                    if let Some(capture_square) =
-                        square_from_coords(square.file as i16 + df, square.rank as i16 + direction)
-                    {
-                        if capture_square == en_passant_target {
-                            if let Some(adjacent_square) =
-                                square_from_coords(square.file as i16 + df, square.rank as i16)
-                            {
-                                if piece_at(state, adjacent_square).is_some_and(|p| {
-                                    p.kind == PieceKind::Pawn && p.color != piece.color
-                                }) {
-                                    moves.push(Move {
-                                        from: square,
-                                        to: capture_square,
-                                        promotion: None,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        PieceKind::Knight => {
-            let deltas = [
-                (-2, -1),
-                (-2, 1),
-                (-1, -2),
-                (-1, 2),
-                (1, -2),
-                (1, 2),
-                (2, -1),
-                (2, 1),
-            ];
-
-            for (df, dr) in deltas {
-                if let Some(to) =
-                    square_from_coords(square.file as i16 + df, square.rank as i16 + dr)
-                {
-                    let target = piece_at(state, to);
-                    if target.is_none() || target.is_some_and(|p| p.color != piece.color) {
-                        moves.push(Move {
-                            from: square,
-                            to,
-                            promotion: None,
-                        });
-                    }
-                }
-            }
-        }
-        PieceKind::Bishop => {
-            generate_sliding_moves(
-                state,
-                square,
-                piece.color,
-                &[(-1, -1), (1, -1), (-1, 1), (1, 1)],
-                &mut moves,
-            );
-        }
-        PieceKind::Rook => {
-            generate_sliding_moves(
-                state,
-                square,
-                piece.color,
-                &[(-1, 0), (1, 0), (0, -1), (0, 1)],
-                &mut moves,
-            );
-        }
-        PieceKind::Queen => {
-            generate_sliding_moves(
-                state,
-                square,
-                piece.color,
-                &[
-                    (-1, -1),
-                    (1, -1),
-                    (-1, 1),
-                    (1, 1),
-                    (-1, 0),
-                    (1, 0),
-                    (0, -1),
-                    (0, 1),
-                ],
-                &mut moves,
-            );
-        }
-        PieceKind::King => {
-            let deltas = [
-                (-1, -1),
-                (0, -1),
-                (1, -1),
-                (-1, 0),
-                (1, 0),
-                (-1, 1),
-                (0, 1),
-                (1, 1),
-            ];
-
-            for (df, dr) in deltas {
-                if let Some(to) =
-                    square_from_coords(square.file as i16 + df, square.rank as i16 + dr)
-                {
-                    let target = piece_at(state, to);
-                    if target.is_none() || target.is_some_and(|p| p.color != piece.color) {
-                        moves.push(Move {
-                            from: square,
-                            to,
-                            promotion: None,
-                        });
-                    }
-                }
-            }
-
-            add_castling_moves(state, square, piece.color, &mut moves);
-        }
+    if piece.kind == PieceKind::King {
+        add_castling_moves(state, position, from, piece.color, moves);
     }
+}
 
+pub fn generate_pseudo_legal_moves_for_square(state: &GameState, square: Square) -> Vec<Move> {
+    let mut moves = MoveList::new();
+    pseudo_legal_moves_from(
+        state,
+        &Position::from_board(&state.board),
+        square_index(square),
+        &mut moves,
+    );
     moves
 }
 
-fn apply_move_unchecked(state: &GameState, mv: &Move) -> GameState {
-    let mut next = state.clone();
-    let Some(mut moving_piece) = piece_at(&next, mv.from) else {
-        return next;
-    };
+// Anything a move's pieces can be relocated on: the board array or its bitboards.
+pub(crate) trait Placement {
+    fn get(&self, square: Square) -> Option<Piece>;
+    fn set(&mut self, square: Square, piece: Option<Piece>);
+}
 
-    let target_before = piece_at(&next, mv.to);
-    let is_en_passant_capture = moving_piece.kind == PieceKind::Pawn
-        && mv.from.file != mv.to.file
-        && target_before.is_none()
-        && state.en_passant_target == Some(mv.to);
+impl Placement for Board {
+    fn get(&self, square: Square) -> Option<Piece> {
+        self[square.rank as usize][square.file as usize]
+    }
 
-    next.board[mv.from.rank as usize][mv.from.file as usize] = None;
+    fn set(&mut self, square: Square, piece: Option<Piece>) {
+        self[square.rank as usize][square.file as usize] = piece;
+    }
+}
 
-    if is_en_passant_capture {
-        let capture_rank = if moving_piece.color == PieceColor::White {
-            mv.to.rank as i16 - 1
-        } else {
-            mv.to.rank as i16 + 1
-        };
+impl Placement for Position {
+    fn get(&self, square: Square) -> Option<Piece> {
+        self.piece_at(square_index(square))
+    }
 
-        if let Some(capture_square) = square_from_coords(mv.to.file as i16, capture_rank) {
-            next.board[capture_square.rank as usize][capture_square.file as usize] = None;
+    fn set(&mut self, square: Square, piece: Option<Piece>) {
+        let index = square_index(square);
+        self.remove(index);
+        if let Some(piece) = piece {
+            self.put(index, piece);
         }
     }
+}
 
-    if moving_piece.kind == PieceKind::King && (mv.from.file as i16 - mv.to.file as i16).abs() == 2
-    {
-        let rank = mv.from.rank;
-        if mv.to.file > mv.from.file {
-            let rook_from = Square { file: 7, rank };
-            let rook_to = Square { file: 5, rank };
-            let rook = next.board[rook_from.rank as usize][rook_from.file as usize];
-            next.board[rook_from.rank as usize][rook_from.file as usize] = None;
-            next.board[rook_to.rank as usize][rook_to.file as usize] = rook;
-        } else {
-            let rook_from = Square { file: 0, rank };
-            let rook_to = Square { file: 3, rank };
-            let rook = next.board[rook_from.rank as usize][rook_from.file as usize];
-            next.board[rook_from.rank as usize][rook_from.file as usize] = None;
-            next.board[rook_to.rank as usize][rook_to.file as usize] = rook;
+// The single place where a move's pieces change squares: the capture (en passant
+// included), the castling rook and the promotion. Returns the moved piece and
+// what was captured where, or `None` if the source square is empty.
+fn move_pieces<P: Placement>(
+    placement: &mut P,
+    mv: &Move,
+    en_passant_target: Option<Square>,
+) -> Option<(Piece, Option<(Square, Piece)>)> {
+    let moved = placement.get(mv.from)?;
+
+    let captured = match placement.get(mv.to) {
+        Some(target) => Some((mv.to, target)),
+        None if moved.kind == PieceKind::Pawn
+            && mv.from.file != mv.to.file
+            && en_passant_target == Some(mv.to) =>
+        {
+            let square = Square {
+                file: mv.to.file,
+                rank: mv.from.rank,
+            };
+            placement.get(square).map(|pawn| (square, pawn))
         }
+        None => None,
+    };
+
+    if let Some((square, _)) = captured {
+        placement.set(square, None);
     }
 
-    if let Some(promotion) = mv.promotion {
-        moving_piece.kind = promotion;
+    if is_castling(moved, mv) {
+        let (rook_from, rook_to) = castling_rook_squares(mv);
+        let rook = placement.get(rook_from);
+        placement.set(rook_from, None);
+        placement.set(rook_to, rook);
     }
 
-    next.board[mv.to.rank as usize][mv.to.file as usize] = Some(moving_piece);
+    let placed = match mv.promotion {
+        Some(kind) => Piece {
+            color: moved.color,
+            kind,
+        },
+        None => moved,
+    };
+    placement.set(mv.from, None);
+    placement.set(mv.to, Some(placed));
 
-    let moved_color = next.active_color;
-    next.active_color = opposite(next.active_color);
+    Some((moved, captured))
+}
 
-    if moved_color == PieceColor::Black {
-        next.fullmove_number += 1;
+// Reverses `move_pieces`, given what it returned.
+pub(crate) fn unmove_pieces<P: Placement>(
+    placement: &mut P,
+    mv: &Move,
+    moved: Piece,
+    captured: Option<(Square, Piece)>,
+) {
+    placement.set(mv.to, None);
+    placement.set(mv.from, Some(moved));
+
+    if is_castling(moved, mv) {
+        let (rook_from, rook_to) = castling_rook_squares(mv);
+        let rook = placement.get(rook_to);
+        placement.set(rook_to, None);
+        placement.set(rook_from, rook);
     }
 
-    if moving_piece.kind == PieceKind::Pawn || target_before.is_some() || is_en_passant_capture {
-        next.halfmove_clock = 0;
-    } else {
-        next.halfmove_clock += 1;
+    if let Some((square, piece)) = captured {
+        placement.set(square, Some(piece));
     }
+}
 
-    next.en_passant_target = if moving_piece.kind == PieceKind::Pawn
-        && (mv.to.rank as i16 - mv.from.rank as i16).abs() == 2
-    {
-        Some(Square {
-            file: mv.from.file,
-            rank: ((mv.from.rank as u16 + mv.to.rank as u16) / 2) as u8,
-        })
-    } else {
-        None
-    };
-
+// Only the piece placement is updated; that is all a king-safety test needs.
+pub(crate) fn position_after(state: &GameState, position: &Position, mv: &Move) -> Position {
+    let mut next = *position;
+    move_pieces(&mut next, mv, state.en_passant_target);
     next
 }
 
-fn find_king_square(state: &GameState, color: PieceColor) -> Option<Square> {
-    for rank in 0u8..8 {
-        for file in 0u8..8 {
-            let square = Square { file, rank };
-            if piece_at(state, square)
-                .is_some_and(|piece| piece.color == color && piece.kind == PieceKind::King)
-            {
-                return Some(square);
-            }
-        }
-    }
-
-    None
+fn is_in_check_at(position: &Position, color: PieceColor) -> bool {
+    position
+        .king_square(color)
+        .is_some_and(|king| position.is_attacked_by(king, opposite(color)))
 }
 
-fn path_clear_between(
+// What a pseudo-legal move by `piece` captures, and on which square.
+pub(crate) fn captured_by(
     state: &GameState,
-    from: Square,
-    to: Square,
-    step_file: i16,
-    step_rank: i16,
-) -> bool {
-    let mut file = from.file as i16 + step_file;
-    let mut rank = from.rank as i16 + step_rank;
-
-    while file != to.file as i16 || rank != to.rank as i16 {
-        let Some(square) = square_from_coords(file, rank) else {
-            return false;
-        };
-
-        if piece_at(state, square).is_some() {
-            return false;
-        }
+    position: &Position,
+    piece: Piece,
+    mv: &Move,
+) -> Option<(usize, Piece)> {
+    let to = square_index(mv.to);
+    if position.color(opposite(piece.color)) & square_bit(mv.to) != 0 {
+        return position.piece_at(to).map(|victim| (to, victim));
+    }
 
-        file += step_file;
-        rank += step_rank;
+    if piece.kind == PieceKind::Pawn
+        && mv.from.file != mv.to.file
+        && state.en_passant_target == Some(mv.to)
+    {
+        let index = square_index(Square {
+            file: mv.to.file,
+            rank: mv.from.rank,
+        });
+        return position.piece_at(index).map(|pawn| (index, pawn));
     }
 
-    true
+    None
 }
 
-fn piece_attacks_square(state: &GameState, from: Square, target: Square, piece: Piece) -> bool {
-    let df = target.file as i16 - from.file as i16;
-    let dr = target.rank as i16 - from.rank as i16;
+// Plays a move on the bitboards given its piece and capture. Every change is an
+// XOR, so calling it again with the same arguments takes the move back.
+pub(crate) fn toggle_move(
+    position: &mut Position,
+    piece: Piece,
+    mv: &Move,
+    captured: Option<(usize, Piece)>,
+) {
+    let placed = Piece {
+        kind: mv.promotion.unwrap_or(piece.kind),
+        ..piece
+    };
+    position.toggle(square_index(mv.from), piece);
+    position.toggle(square_index(mv.to), placed);
 
-    match piece.kind {
-        PieceKind::Pawn => {
-            let direction = if piece.color == PieceColor::White {
-                1
-            } else {
-                -1
-            };
-            dr == direction && (df == -1 || df == 1)
-        }
-        PieceKind::Knight => {
-            let adf = df.abs();
-            let adr = dr.abs();
-            (adf == 1 && adr == 2) || (adf == 2 && adr == 1)
-        }
-        PieceKind::Bishop => {
-            if df.abs() != dr.abs() || df == 0 {
-                return false;
-            }
-            let step_file = if df > 0 { 1 } else { -1 };
-            let step_rank = if dr > 0 { 1 } else { -1 };
-            path_clear_between(state, from, target, step_file, step_rank)
-        }
-        PieceKind::Rook => {
-            if df != 0 && dr != 0 {
-                return false;
-            }
+    if let Some((index, victim)) = captured {
+        position.toggle(index, victim);
+    }
 
-            if df == 0 {
-                let step_rank = if dr > 0 { 1 } else { -1 };
-                path_clear_between(state, from, target, 0, step_rank)
-            } else {
-                let step_file = if df > 0 { 1 } else { -1 };
-                path_clear_between(state, from, target, step_file, 0)
-            }
-        }
-        PieceKind::Queen => {
-            if df == 0 || dr == 0 {
-                let step_file = if df == 0 {
-                    0
-                } else if df > 0 {
-                    1
-                } else {
-                    -1
-                };
-                let step_rank = if dr == 0 {
-                    0
-                } else if dr > 0 {
-                    1
-                } else {
-                    -1
-                };
-                path_clear_between(state, from, target, step_file, step_rank)
-            } else if df.abs() == dr.abs() {
-                let step_file = if df > 0 { 1 } else { -1 };
-                let step_rank = if dr > 0 { 1 } else { -1 };
-                path_clear_between(state, from, target, step_file, step_rank)
-            } else {
-                false
-            }
-        }
-        PieceKind::King => df.abs() <= 1 && dr.abs() <= 1,
+    if is_castling(piece, mv) {
+        let (rook_from, rook_to) = castling_rook_squares(mv);
+        let rook = Piece {
+            kind: PieceKind::Rook,
+            ..piece
+        };
+        position.toggle(square_index(rook_from), rook);
+        position.toggle(square_index(rook_to), rook);
     }
 }
 
-pub fn is_square_attacked_by(state: &GameState, target: Square, by_color: PieceColor) -> bool {
-    for rank in 0u8..8 {
-        for file in 0u8..8 {
-            let from = Square { file, rank };
-            let Some(piece) = piece_at(state, from) else {
-                continue;
-            };
+// Candidates are played and taken back on the caller's bitboards, which are left
+// as they were found.
+fn legal_moves_from(state: &GameState, position: &mut Position, from: usize, moves: &mut MoveList) {
+    let Some(piece) = position.piece_at(from) else {
+        return;
+    };
 
-            if piece.color != by_color {
-                continue;
-            }
+    if piece.color != state.active_color {
+        return;
+    }
 
-            if piece_attacks_square(state, from, target, piece) {
-                return true;
-            }
+    let mut pseudo_legal = MoveList::new();
+    pseudo_legal_moves_from(state, position, from, &mut pseudo_legal);
+    for mv in pseudo_legal {
+        let captured = captured_by(state, position, piece, &mv);
+        toggle_move(position, piece, &mv, captured);
+        let legal = !is_in_check_at(position, piece.color);
+        toggle_move(position, piece, &mv, captured);
+        if legal {
+            moves.push(mv);
         }
     }
+}
 
-    false
+// Everything `unmake_move` needs to restore the state `make_move` was given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveUndo {
+    pub mv: Move,
+    pub moved: Piece,
+    pub captured: Option<(Square, Piece)>,
+    pub castling_rights: CastlingRights,
+    pub en_passant_target: Option<Square>,
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
 }
 
-pub fn is_in_check_for_color(state: &GameState, color: PieceColor) -> bool {
-    let Some(king_square) = find_king_square(state, color) else {
-        return false;
-    };
+pub(crate) fn castling_rook_squares(mv: &Move) -> (Square, Square) {
+    let rank = mv.from.rank;
+    if mv.to.file > mv.from.file {
+        (Square { file: 7, rank }, Square { file: 5, rank })
+    } else {
+        (Square { file: 0, rank }, Square { file: 3, rank })
+    }
+}
 
-    is_square_attacked_by(state, king_square, opposite(color))
+pub(crate) fn is_castling(piece: Piece, mv: &Move) -> bool {
+    piece.kind == PieceKind::King && mv.from.file.abs_diff(mv.to.file) == 2
 }
 
-pub fn generate_legal_moves_for_square(state: &GameState, square: Square) -> Vec<Move> {
-    let Some(piece) = piece_at(state, square) else {
-        return Vec::new();
+// Plays a pseudo-legal move in place, or returns `None` if the source square is
+// empty. The status is left as it was; callers that need it re-evaluate after the move.
+pub fn make_move(state: &mut GameState, mv: &Move) -> Option<MoveUndo> {
+    let (moved, captured) = move_pieces(&mut state.board, mv, state.en_passant_target)?;
+
+    let undo = MoveUndo {
+        mv: mv.clone(),
+        moved,
+        captured,
+        castling_rights: state.castling_rights,
+        en_passant_target: state.en_passant_target,
+        halfmove_clock: state.halfmove_clock,
+        fullmove_number: state.fullmove_number,
     };
 
-    if piece.color != state.active_color {
-        return Vec::new();
+    state.castling_rights = update_castling_rights(
+        state.castling_rights,
+        moved,
+        mv.from,
+        mv.to,
+        captured.is_some(),
+    );
+
+    state.en_passant_target =
+        if moved.kind == PieceKind::Pawn && mv.from.rank.abs_diff(mv.to.rank) == 2 {
+            Some(Square {
+                file: mv.from.file,
+                rank: (mv.from.rank + mv.to.rank) / 2,
+            })
+        } else {
+            None
+        };
+
+    if moved.kind == PieceKind::Pawn || captured.is_some() {
+        state.halfmove_clock = 0;
+    } else {
+        state.halfmove_clock += 1;
     }
 
-    generate_pseudo_legal_moves_for_square(state, square)
-        .into_iter()
-        .filter(|mv| {
-            let next = apply_move_unchecked(state, mv);
-            !is_in_check_for_color(&next, piece.color)
-        })
-        .collect()
+    if moved.color == PieceColor::Black {
+        state.fullmove_number += 1;
+    }
+    state.active_color = opposite(moved.color);
+
+    Some(undo)
+}
+
+pub fn unmake_move(state: &mut GameState, undo: MoveUndo) {
+    unmove_pieces(&mut state.board, &undo.mv, undo.moved, undo.captured);
+
+    state.active_color = undo.moved.color;
+    state.castling_rights = undo.castling_rights;
+    state.en_passant_target = undo.en_passant_target;
+    state.halfmove_clock = undo.halfmove_clock;
+    state.fullmove_number = undo.fullmove_number;
+}
+
+pub fn is_square_attacked_by(state: &GameState, target: Square, by_color: PieceColor) -> bool {
+    Position::from_board(&state.board).is_attacked_by(square_index(target), by_color)
+}
+
+pub fn is_in_check_for_color(state: &GameState, color: PieceColor) -> bool {
+    is_in_check_at(&Position::from_board(&state.board), color)
+}
+
+pub fn generate_legal_moves_for_square(state: &GameState, square: Square) -> Vec<Move> {
+    let mut moves = MoveList::new();
+    legal_moves_from(
+        state,
+        &mut Position::from_board(&state.board),
+        square_index(square),
+        &mut moves,
+    );
+    moves
 }
 
 pub fn is_legal_move(state: &GameState, from: Square, to: Square) -> bool {
@@ -614,17 +486,55 @@ pub fn is_legal_move(state: &GameState, from: Square, to: Square) -> bool {
         .any(|mv| mv.to == to)
 }
 
-pub fn side_to_move_has_any_move(state: &GameState) -> bool {
-    for rank in 0u8..8 {
-        for file in 0u8..8 {
-            let square = Square { file, rank };
-            if !generate_legal_moves_for_square(state, square).is_empty() {
-                return true;
-            }
-        }
+pub fn generate_legal_moves(state: &GameState) -> MoveList {
+    let mut position = Position::from_board(&state.board);
+    let mut moves = MoveList::new();
+    for from in squares(position.color(state.active_color)) {
+        legal_moves_from(state, &mut position, from, &mut moves);
     }
+    moves
+}
 
-    false
+pub fn is_capture(state: &GameState, mv: &Move) -> bool {
+    piece_at(state, mv.to).is_some()
+        || piece_at(state, mv.from)
+            .is_some_and(|piece| piece.kind == PieceKind::Pawn && mv.from.file != mv.to.file)
+}
+
+pub fn gives_check(state: &GameState, mv: &Move) -> bool {
+    let position = position_after(state, &Position::from_board(&state.board), mv);
+    is_in_check_at(&position, opposite(state.active_color))
+}
+
+pub fn generate_legal_captures(state: &GameState) -> MoveList {
+    generate_legal_moves(state)
+        .into_iter()
+        .filter(|mv| is_capture(state, mv))
+        .collect()
+}
+
+// Quiet moves neither capture nor promote.
+pub fn generate_legal_quiet_moves(state: &GameState) -> MoveList {
+    generate_legal_moves(state)
+        .into_iter()
+        .filter(|mv| mv.promotion.is_none() && !is_capture(state, mv))
+        .collect()
+}
+
+pub fn generate_legal_checks(state: &GameState) -> MoveList {
+    generate_legal_moves(state)
+        .into_iter()
+        .filter(|mv| gives_check(state, mv))
+        .collect()
+}
+
+pub fn side_to_move_has_any_move(state: &GameState) -> bool {
+    let mut position = Position::from_board(&state.board);
+    let mut moves = MoveList::new();
+    squares(position.color(state.active_color)).any(|from| {
+        legal_moves_from(state, &mut position, from, &mut moves);
+        !moves.is_empty()
+    })
 }
 
 pub fn opposite(color: PieceColor) -> PieceColor {
@@ -0,0 +1,45 @@
+use crate::game::{GameState, Move};
+use crate::move_gen::{generate_legal_moves, make_move, unmake_move};
+
+// Counts leaf nodes of the legal move tree. Draw rules are ignored, so the
+// counts match the published reference values.
+pub fn perft(state: &GameState, depth: u32) -> u64 {
+    perft_in_place(&mut state.clone(), depth)
+}
+
+fn perft_in_place(state: &mut GameState, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves = generate_legal_moves(state);
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+
+    let mut nodes = 0;
+    for mv in &moves {
+        let undo = make_move(state, mv).expect("legal move");
+        nodes += perft_in_place(state, depth - 1);
+        unmake_move(state, undo);
+    }
+    nodes
+}
+
+// Per-root-move node counts, for bisecting a mismatch against another engine.
+pub fn perft_divide(state: &GameState, depth: u32) -> Vec<(Move, u64)> {
+    if depth == 0 {
+        return Vec::new();
+    }
+
+    let mut scratch = state.clone();
+    generate_legal_moves(state)
+        .into_iter()
+        .map(|mv| {
+            let undo = make_move(&mut scratch, &mv).expect("legal move");
+            let nodes = perft_in_place(&mut scratch, depth - 1);
+            unmake_move(&mut scratch, undo);
+            (mv, nodes)
+        })
+        .collect()
+}
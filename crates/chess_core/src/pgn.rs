@@ -0,0 +1,338 @@
+use thiserror::Error;
+
+use crate::board::PieceColor;
+use crate::fen::{from_fen, to_fen};
+use crate::game::{apply_move_to_state, initial_state, EngineError, GameState, GameStatus, Move};
+use crate::san::{parse_san, to_san, SanError};
+
+// The seven-tag roster, in the order export must emit it.
+pub const SEVEN_TAG_ROSTER: [&str; 7] =
+    ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+const MAX_LINE_WIDTH: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgnResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    Unknown,
+}
+
+impl PgnResult {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::WhiteWins => "1-0",
+            Self::BlackWins => "0-1",
+            Self::Draw => "1/2-1/2",
+            Self::Unknown => "*",
+        }
+    }
+
+    pub fn from_status(status: &GameStatus) -> Self {
+        match status.result() {
+            None => Self::Unknown,
+            Some(result) => match result.winner {
+                Some(PieceColor::White) => Self::WhiteWins,
+                Some(PieceColor::Black) => Self::BlackWins,
+                None => Self::Draw,
+            },
+        }
+    }
+
+    pub fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "1-0" => Some(Self::WhiteWins),
+            "0-1" => Some(Self::BlackWins),
+            "1/2-1/2" => Some(Self::Draw),
+            "*" => Some(Self::Unknown),
+            _ => None,
+        }
+    }
+}
+
+pub type PgnTags = Vec<(String, String)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnGame {
+    pub tags: PgnTags,
+    pub initial_state: GameState,
+    pub moves: Vec<Move>,
+    pub state: GameState,
+    pub result: PgnResult,
+}
+
+impl PgnGame {
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn to_pgn(&self) -> String {
+        let mut pgn = String::new();
+
+        for name in SEVEN_TAG_ROSTER {
+            let value = match name {
+                "Result" => self.result.as_str(),
+                "Date" => self.tag(name).unwrap_or("????.??.??"),
+                _ => self.tag(name).unwrap_or("?"),
+            };
+            push_tag(&mut pgn, name, value);
+        }
+
+        if self.initial_state != initial_state() {
+            push_tag(&mut pgn, "SetUp", "1");
+            push_tag(&mut pgn, "FEN", &to_fen(&self.initial_state));
+        }
+
+        for (name, value) in &self.tags {
+            if !SEVEN_TAG_ROSTER.contains(&name.as_str()) && name != "SetUp" && name != "FEN" {
+                push_tag(&mut pgn, name, value);
+            }
+        }
+
+        pgn.push('\n');
+        pgn.push_str(&self.movetext());
+        pgn.push('\n');
+        pgn
+    }
+
+    fn movetext(&self) -> String {
+        let mut tokens = Vec::new();
+        let mut state = self.initial_state.clone();
+
+        for (index, mv) in self.moves.iter().enumerate() {
+            if state.active_color == PieceColor::White {
+                tokens.push(format!("{}.", state.fullmove_number));
+            } else if index == 0 {
+                tokens.push(format!("{}...", state.fullmove_number));
+            }
+
+            tokens.push(to_san(&state, mv));
+            match apply_move_to_state(&state, mv.clone().into()) {
+                Ok(result) => state = result.state,
+                Err(_) => break,
+            }
+        }
+
+        tokens.push(self.result.as_str().to_string());
+        wrap_tokens(&tokens)
+    }
+}
+
+fn push_tag(pgn: &mut String, name: &str, value: &str) {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    pgn.push_str(&format!("[{name} \"{escaped}\"]\n"));
+}
+
+fn wrap_tokens(tokens: &[String]) -> String {
+    let mut text = String::new();
+    let mut line_len = 0;
+
+    for token in tokens {
+        if line_len > 0 && line_len + 1 + token.len() > MAX_LINE_WIDTH {
+            text.push('\n');
+            line_len = 0;
+        } else if line_len > 0 {
+            text.push(' ');
+            line_len += 1;
+        }
+
+        text.push_str(token);
+        line_len += token.len();
+    }
+
+    text
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PgnError {
+    #[error("malformed tag pair near '{0}'")]
+    InvalidTag(String),
+    #[error("unterminated comment or variation")]
+    Unterminated,
+    #[error("invalid FEN tag: {0}")]
+    InvalidSetup(EngineError),
+    #[error("illegal move '{token}' at ply {ply}: {source}")]
+    IllegalMove {
+        ply: usize,
+        token: String,
+        source: SanError,
+    },
+}
+
+pub fn parse_pgn(pgn: &str) -> Result<PgnGame, PgnError> {
+    let (tags, movetext) = parse_tags(pgn)?;
+
+    let initial_state = match tags.iter().find(|(key, _)| key == "FEN") {
+        Some((_, fen)) => from_fen(fen).map_err(PgnError::InvalidSetup)?,
+        None => initial_state(),
+    };
+
+    let mut state = initial_state.clone();
+    let mut moves = Vec::new();
+    let mut result = None;
+
+    for token in tokenize_movetext(movetext)? {
+        if let Some(token_result) = PgnResult::from_token(token) {
+            result = Some(token_result);
+            break;
+        }
+
+        let ply = moves.len() + 1;
+        let illegal = |source: SanError| PgnError::IllegalMove {
+            ply,
+            token: token.to_string(),
+            source,
+        };
+
+        let mv = parse_san(&state, token).map_err(illegal)?;
+        let applied = apply_move_to_state(&state, mv.into())
+            .map_err(|_| illegal(SanError::IllegalMove(token.to_string())))?;
+
+        state = applied.state;
+        moves.push(applied.move_applied);
+    }
+
+    let result = result
+        .or_else(|| {
+            tags.iter()
+                .find(|(key, _)| key == "Result")
+                .and_then(|(_, value)| PgnResult::from_token(value))
+        })
+        .unwrap_or(PgnResult::Unknown);
+
+    Ok(PgnGame {
+        tags,
+        initial_state,
+        moves,
+        state,
+        result,
+    })
+}
+
+fn parse_tags(pgn: &str) -> Result<(PgnTags, &str), PgnError> {
+    let mut tags = Vec::new();
+    let mut rest = pgn.trim_start();
+
+    while let Some(body) = rest.strip_prefix('[') {
+        let invalid = || PgnError::InvalidTag(body.lines().next().unwrap_or("").to_string());
+
+        let body = body.trim_start();
+        let name_len = body
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .ok_or_else(invalid)?;
+        if name_len == 0 {
+            return Err(invalid());
+        }
+        let name = &body[..name_len];
+
+        let after_name = body[name_len..].trim_start();
+        let mut chars = after_name
+            .strip_prefix('"')
+            .ok_or_else(invalid)?
+            .char_indices();
+        let mut value = String::new();
+        let mut value_end = None;
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' => value.push(chars.next().ok_or_else(invalid)?.1),
+                '"' => {
+                    value_end = Some(index);
+                    break;
+                }
+                _ => value.push(c),
+            }
+        }
+
+        let value_end = value_end.ok_or_else(invalid)?;
+        let after_value = after_name[1 + value_end + 1..].trim_start();
+        rest = after_value
+            .strip_prefix(']')
+            .ok_or_else(invalid)?
+            .trim_start();
+
+        tags.push((name.to_string(), value));
+    }
+
+    Ok((tags, rest))
+}
+
+fn tokenize_movetext(movetext: &str) -> Result<Vec<&str>, PgnError> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut comment_end = None;
+    let mut variation_depth = 0usize;
+    let mut at_line_start = true;
+    let mut chars = movetext.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if let Some(end) = comment_end {
+            if c == end {
+                comment_end = None;
+            }
+            at_line_start = c == '\n';
+            continue;
+        }
+
+        let is_separator = c.is_whitespace() || matches!(c, '{' | '}' | ';' | '(' | ')');
+        if is_separator {
+            if let Some(token_start) = start.take() {
+                if variation_depth == 0 {
+                    tokens.push(&movetext[token_start..index]);
+                }
+            }
+        }
+
+        match c {
+            '{' => comment_end = Some('}'),
+            ';' => comment_end = Some('\n'),
+            '%' if at_line_start => comment_end = Some('\n'),
+            '(' => variation_depth += 1,
+            ')' => variation_depth = variation_depth.saturating_sub(1),
+            _ if is_separator => {}
+            _ => {
+                if start.is_none() {
+                    start = Some(index);
+                }
+            }
+        }
+
+        at_line_start = c == '\n';
+        if chars.peek().is_none() {
+            if let Some(token_start) = start.take() {
+                if variation_depth == 0 {
+                    tokens.push(&movetext[token_start..]);
+                }
+            }
+        }
+    }
+
+    if comment_end == Some('}') || variation_depth > 0 {
+        return Err(PgnError::Unterminated);
+    }
+
+    Ok(tokens
+        .into_iter()
+        .filter_map(strip_move_number)
+        .filter(|token| !token.starts_with('$'))
+        .collect())
+}
+
+// Move numbers may be separate ("12.", "12...") or glued to the move ("12.e4").
+fn strip_move_number(token: &str) -> Option<&str> {
+    let digits = token.len() - token.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let after_digits = &token[digits..];
+    if digits == 0 || !after_digits.starts_with('.') {
+        return Some(token);
+    }
+
+    let rest = after_digits.trim_start_matches('.');
+    if rest.is_empty() {
+        None
+    } else {
+        Some(rest)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::board::{Board, PieceColor, PieceKind, Square};
+use crate::game::{CastlingRights, GameState};
+use crate::move_gen::generate_legal_moves_for_square;
+
+pub const THREEFOLD: usize = 3;
+pub const FIVEFOLD: usize = 5;
+
+// Two positions are the same for repetition purposes when these fields match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionKey {
+    pub board: Board,
+    pub active_color: PieceColor,
+    pub castling_rights: CastlingRights,
+    pub en_passant_target: Option<Square>,
+}
+
+// An en passant target only distinguishes positions if a capture on it is legal.
+pub fn capturable_en_passant_target(state: &GameState) -> Option<Square> {
+    let target = state.en_passant_target?;
+    let pawn_rank = match state.active_color {
+        PieceColor::White => target.rank.checked_sub(1)?,
+        PieceColor::Black => target.rank + 1,
+    };
+
+    [target.file.checked_sub(1), Some(target.file + 1)]
+        .into_iter()
+        .flatten()
+        .filter_map(|file| Square::new(file, pawn_rank))
+        .filter(|from| {
+            state.board[from.rank as usize][from.file as usize].is_some_and(|piece| {
+                piece.kind == PieceKind::Pawn && piece.color == state.active_color
+            })
+        })
+        .any(|from| {
+            generate_legal_moves_for_square(state, from)
+                .iter()
+                .any(|mv| mv.to == target)
+        })
+        .then_some(target)
+}
+
+pub fn position_key(state: &GameState) -> PositionKey {
+    PositionKey {
+        board: state.board,
+        active_color: state.active_color,
+        castling_rights: state.castling_rights,
+        en_passant_target: capturable_en_passant_target(state),
+    }
+}
+
+// `positions` runs from the start of the game up to and including the current position.
+pub fn repetition_count<'a, I>(positions: I) -> usize
+where
+    I: DoubleEndedIterator<Item = &'a GameState>,
+{
+    let mut positions = positions.rev();
+    let Some(current) = positions.next() else {
+        return 0;
+    };
+
+    let key = position_key(current);
+    // Captures and pawn moves are irreversible, so nothing before them can repeat.
+    let reversible_plies = current.halfmove_clock as usize;
+
+    1 + positions
+        .take(reversible_plies)
+        .filter(|state| position_key(state) == key)
+        .count()
+}
@@ -1,7 +1,10 @@
-use crate::board::PieceColor;
-use crate::game::{GameState, GameStatus};
+use crate::board::{PieceColor, PieceKind};
+use crate::game::{DrawReason, GameState, GameStatus};
 use crate::move_gen::{is_in_check_for_color, opposite, side_to_move_has_any_move};
 
+pub const FIFTY_MOVE_RULE_PLIES: u32 = 100;
+pub const SEVENTY_FIVE_MOVE_RULE_PLIES: u32 = 150;
+
 pub fn is_in_check(state: &GameState, color: PieceColor) -> bool {
     is_in_check_for_color(state, color)
 }
@@ -9,13 +12,98 @@ pub fn is_in_check(state: &GameState, color: PieceColor) -> bool {
 pub fn evaluate_status(state: &GameState) -> GameStatus {
     let active = state.active_color;
 
-    if side_to_move_has_any_move(state) {
-        return GameStatus::InProgress;
+    // Mate and stalemate take precedence, so a mate on the 75th move still stands.
+    if !side_to_move_has_any_move(state) {
+        return if is_in_check(state, active) {
+            GameStatus::Checkmate(opposite(active))
+        } else {
+            GameStatus::Stalemate(active)
+        };
+    }
+
+    if has_insufficient_material(state) {
+        return GameStatus::Draw(DrawReason::InsufficientMaterial);
     }
 
-    if is_in_check(state, active) {
-        GameStatus::Checkmate(opposite(active))
-    } else {
-        GameStatus::Stalemate(active)
+    if state.halfmove_clock >= SEVENTY_FIVE_MOVE_RULE_PLIES {
+        return GameStatus::Draw(DrawReason::SeventyFiveMoveRule);
     }
+
+    GameStatus::InProgress
+}
+
+pub fn can_claim_fifty_move_rule(state: &GameState) -> bool {
+    state.status == GameStatus::InProgress && state.halfmove_clock >= FIFTY_MOVE_RULE_PLIES
+}
+
+fn is_light_square(rank: usize, file: usize) -> bool {
+    (rank + file) % 2 == 1
+}
+
+// True when `color` cannot deliver mate by any sequence of legal moves,
+// even with the opponent's cooperation.
+pub fn has_insufficient_material_to_win(state: &GameState, color: PieceColor) -> bool {
+    let mut own_knights = 0;
+    let mut own_bishops = 0;
+    let mut opponent_blockers = 0;
+    let mut any_pawns = false;
+    let mut any_knights = false;
+    let mut light_bishops = false;
+    let mut dark_bishops = false;
+
+    for (rank, row) in state.board.iter().enumerate() {
+        for (file, piece) in row.iter().enumerate() {
+            let Some(piece) = piece else {
+                continue;
+            };
+
+            match piece.kind {
+                PieceKind::Pawn | PieceKind::Rook | PieceKind::Queen if piece.color == color => {
+                    return false;
+                }
+                PieceKind::Pawn => any_pawns = true,
+                PieceKind::Knight => any_knights = true,
+                PieceKind::Bishop => {
+                    if is_light_square(rank, file) {
+                        light_bishops = true;
+                    } else {
+                        dark_bishops = true;
+                    }
+                }
+                _ => {}
+            }
+
+            if piece.color == color {
+                match piece.kind {
+                    PieceKind::Knight => own_knights += 1,
+                    PieceKind::Bishop => own_bishops += 1,
+                    _ => {}
+                }
+            } else if !matches!(piece.kind, PieceKind::King | PieceKind::Queen) {
+                // Anything but a queen can end up blocking the king's escape squares.
+                opponent_blockers += 1;
+            }
+        }
+    }
+
+    if own_knights > 0 {
+        return own_knights + own_bishops == 1 && opponent_blockers == 0;
+    }
+
+    if own_bishops > 0 {
+        let bishops_share_color = !(light_bishops && dark_bishops);
+        return bishops_share_color && !any_knights && !any_pawns;
+    }
+
+    true
+}
+
+pub fn has_insufficient_material(state: &GameState) -> bool {
+    has_insufficient_material_to_win(state, PieceColor::White)
+        && has_insufficient_material_to_win(state, PieceColor::Black)
+}
+
+// Used when a flag falls: the opponent only wins if they could still mate.
+pub fn can_win_on_time(state: &GameState, color: PieceColor) -> bool {
+    !has_insufficient_material_to_win(state, color)
 }
@@ -0,0 +1,261 @@
+use thiserror::Error;
+
+use crate::board::{Piece, PieceColor, PieceKind, Square};
+use crate::game::{GameState, Move};
+use crate::move_gen::{
+    generate_legal_moves_for_square, is_capture, is_castling, is_in_check_for_color, make_move,
+    piece_at, side_to_move_has_any_move,
+};
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum SanError {
+    #[error("empty move")]
+    Empty,
+    #[error("invalid SAN syntax: {0}")]
+    InvalidSyntax(String),
+    #[error("illegal move: {0}")]
+    IllegalMove(String),
+    #[error("ambiguous move: {0}")]
+    AmbiguousMove(String),
+}
+
+fn file_char(square: Square) -> char {
+    (square.file + b'a') as char
+}
+
+fn rank_char(square: Square) -> char {
+    (square.rank + b'1') as char
+}
+
+fn disambiguation(state: &GameState, piece: Piece, mv: &Move) -> String {
+    let mut rivals = Vec::new();
+    for rank in 0u8..8 {
+        for file in 0u8..8 {
+            let square = Square { file, rank };
+            if square == mv.from || piece_at(state, square) != Some(piece) {
+                continue;
+            }
+
+            if generate_legal_moves_for_square(state, square)
+                .iter()
+                .any(|other| other.to == mv.to)
+            {
+                rivals.push(square);
+            }
+        }
+    }
+
+    if rivals.is_empty() {
+        String::new()
+    } else if rivals.iter().all(|square| square.file != mv.from.file) {
+        file_char(mv.from).to_string()
+    } else if rivals.iter().all(|square| square.rank != mv.from.rank) {
+        rank_char(mv.from).to_string()
+    } else {
+        mv.from.to_algebraic()
+    }
+}
+
+pub fn to_san(state: &GameState, mv: &Move) -> String {
+    let Some(piece) = piece_at(state, mv.from) else {
+        return format!("{}{}", mv.from.to_algebraic(), mv.to.to_algebraic());
+    };
+
+    let mut san = String::new();
+
+    if is_castling(piece, mv) {
+        san.push_str(if mv.to.file > mv.from.file {
+            "O-O"
+        } else {
+            "O-O-O"
+        });
+    } else {
+        let is_capture = is_capture(state, mv);
+
+        if piece.kind == PieceKind::Pawn {
+            if is_capture {
+                san.push(file_char(mv.from));
+            }
+        } else {
+            san.push(piece.kind.to_char().to_ascii_uppercase());
+            san.push_str(&disambiguation(state, piece, mv));
+        }
+
+        if is_capture {
+            san.push('x');
+        }
+        san.push_str(&mv.to.to_algebraic());
+
+        if let Some(promotion) = mv.promotion {
+            san.push('=');
+            san.push(promotion.to_char().to_ascii_uppercase());
+        }
+    }
+
+    // Read off the resulting position rather than its status, which may record
+    // a draw or other ending that does not change whether the move gives check.
+    let mut next = state.clone();
+    if make_move(&mut next, mv).is_some() && is_in_check_for_color(&next, next.active_color) {
+        san.push(if side_to_move_has_any_move(&next) {
+            '+'
+        } else {
+            '#'
+        });
+    }
+
+    san
+}
+
+struct SanPattern {
+    kind: PieceKind,
+    from_file: Option<u8>,
+    from_rank: Option<u8>,
+    to: Square,
+    promotion: Option<PieceKind>,
+    capture: bool,
+}
+
+fn parse_pattern(text: &str) -> Option<SanPattern> {
+    let capture = text.contains(['x', ':']);
+    let mut chars: Vec<char> = text.chars().filter(|c| *c != 'x' && *c != ':').collect();
+
+    let kind = match chars.first() {
+        Some(c @ ('N' | 'B' | 'R' | 'Q' | 'K' | 'P')) => {
+            let kind = PieceKind::from_char(*c)?;
+            chars.remove(0);
+            kind
+        }
+        _ => PieceKind::Pawn,
+    };
+
+    // Promotion may be written "e8=Q", "e8Q" or "e8=q".
+    let mut promotion = None;
+    if chars.last().is_some_and(|c| c.is_ascii_alphabetic()) {
+        let promoted = PieceKind::from_char(chars.pop()?)?;
+        if kind != PieceKind::Pawn || matches!(promoted, PieceKind::Pawn | PieceKind::King) {
+            return None;
+        }
+        promotion = Some(promoted);
+        if chars.last() == Some(&'=') {
+            chars.pop();
+        }
+    }
+
+    if chars.len() < 2 {
+        return None;
+    }
+    let destination: String = chars.split_off(chars.len() - 2).into_iter().collect();
+    let to = Square::from_algebraic(&destination)?;
+
+    let mut from_file = None;
+    let mut from_rank = None;
+    for c in chars {
+        match c {
+            'a'..='h' if from_file.is_none() && from_rank.is_none() => {
+                from_file = Some(c as u8 - b'a')
+            }
+            '1'..='8' if from_rank.is_none() => from_rank = Some(c as u8 - b'1'),
+            _ => return None,
+        }
+    }
+
+    Some(SanPattern {
+        kind,
+        from_file,
+        from_rank,
+        to,
+        promotion,
+        capture,
+    })
+}
+
+fn parse_castling(text: &str) -> Option<bool> {
+    match text.replace('0', "O").to_ascii_uppercase().as_str() {
+        "O-O" => Some(true),
+        "O-O-O" => Some(false),
+        _ => None,
+    }
+}
+
+fn find_castling_move(state: &GameState, king_side: bool) -> Option<Move> {
+    let rank = match state.active_color {
+        PieceColor::White => 0,
+        PieceColor::Black => 7,
+    };
+    let from = Square { file: 4, rank };
+    let to = Square {
+        file: if king_side { 6 } else { 2 },
+        rank,
+    };
+
+    if !piece_at(state, from).is_some_and(|piece| piece.kind == PieceKind::King) {
+        return None;
+    }
+
+    generate_legal_moves_for_square(state, from)
+        .into_iter()
+        .find(|mv| mv.to == to)
+}
+
+pub fn parse_san(state: &GameState, san: &str) -> Result<Move, SanError> {
+    let text = san.trim().trim_end_matches(['+', '#', '!', '?']);
+    if text.is_empty() {
+        return Err(SanError::Empty);
+    }
+
+    if let Some(king_side) = parse_castling(text) {
+        return find_castling_move(state, king_side)
+            .ok_or_else(|| SanError::IllegalMove(san.to_string()));
+    }
+
+    let pattern = parse_pattern(text).ok_or_else(|| SanError::InvalidSyntax(san.to_string()))?;
+
+    let mut candidates = Vec::new();
+    for rank in 0u8..8 {
+        for file in 0u8..8 {
+            if pattern.from_file.is_some_and(|f| f != file)
+                || pattern.from_rank.is_some_and(|r| r != rank)
+            {
+                continue;
+            }
+
+            let from = Square { file, rank };
+            let Some(piece) = piece_at(state, from) else {
+                continue;
+            };
+            if piece.kind != pattern.kind || piece.color != state.active_color {
+                continue;
+            }
+
+            for mv in generate_legal_moves_for_square(state, from) {
+                // A pawn only leaves its file to capture, and a capture names that file.
+                if piece.kind == PieceKind::Pawn
+                    && pattern.from_file.is_none()
+                    && mv.from.file != mv.to.file
+                {
+                    continue;
+                }
+                if pattern.capture && !is_capture(state, &mv) {
+                    continue;
+                }
+
+                // A missing promotion piece defaults to a queen, as in MoveInput.
+                let promotion_matches = match (mv.promotion, pattern.promotion) {
+                    (None, None) => true,
+                    (None, Some(_)) => false,
+                    (Some(kind), requested) => kind == requested.unwrap_or(PieceKind::Queen),
+                };
+
+                if mv.to == pattern.to && promotion_matches && !is_castling(piece, &mv) {
+                    candidates.push(mv);
+                }
+            }
+        }
+    }
+
+    match candidates.len() {
+        0 => Err(SanError::IllegalMove(san.to_string())),
+        1 => Ok(candidates.remove(0)),
+        _ => Err(SanError::AmbiguousMove(san.to_string())),
+    }
+}
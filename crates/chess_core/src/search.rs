@@ -0,0 +1,420 @@
+use serde::{Deserialize, Serialize};
+
+use crate::board::PieceKind;
+use crate::eval::{evaluate, piece_value};
+use crate::game::{GameState, GameStatus, Move};
+use crate::move_gen::{
+    generate_legal_moves, is_capture, is_in_check_for_color, make_move, unmake_move, MoveUndo,
+};
+use crate::rules::{has_insufficient_material, FIFTY_MOVE_RULE_PLIES};
+use crate::transposition::{Bound, TranspositionTable};
+use crate::zobrist::{hash, hash_after_move, ZobristHash};
+
+// Scores are centipawns from the side to move's point of view. Mate is
+// `MATE_SCORE` minus the distance in plies, so shorter mates score higher.
+pub const MATE_SCORE: i32 = 32_000;
+pub const MAX_PLY: usize = 128;
+
+const INFINITY: i32 = MATE_SCORE + 1;
+// How often, in nodes, the clock is read for the time limit.
+const LIMIT_CHECK_INTERVAL: u64 = 1024;
+// Size of the throwaway table behind `search`; callers that search repeatedly
+// should keep their own table and use `search_with_table`.
+const ONE_OFF_TABLE_MB: usize = 1;
+
+// Unset limits do not apply; with none at all the search runs to `MAX_PLY`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchLimits {
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    pub time_ms: Option<u64>,
+}
+
+impl SearchLimits {
+    pub fn depth(depth: u32) -> Self {
+        Self {
+            depth: Some(depth),
+            ..Self::default()
+        }
+    }
+
+    pub fn nodes(nodes: u64) -> Self {
+        Self {
+            nodes: Some(nodes),
+            ..Self::default()
+        }
+    }
+
+    pub fn time_ms(time_ms: u64) -> Self {
+        Self {
+            time_ms: Some(time_ms),
+            ..Self::default()
+        }
+    }
+}
+
+// `depth` is the last fully searched depth, 0 if the limits cut even the first
+// iteration short; `best_move` is `None` only when the game is already over.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub pv: Vec<Move>,
+    pub depth: u32,
+    pub nodes: u64,
+}
+
+impl SearchResult {
+    pub fn is_mate(&self) -> bool {
+        is_mate_score(self.score)
+    }
+
+    // Full moves until mate: positive when the side to move mates, negative when it is mated.
+    pub fn mate_in(&self) -> Option<i32> {
+        if !self.is_mate() {
+            return None;
+        }
+        let plies = MATE_SCORE - self.score.abs();
+        let moves = (plies + 1) / 2;
+        Some(if self.score > 0 { moves } else { -moves })
+    }
+}
+
+pub fn is_mate_score(score: i32) -> bool {
+    score.abs() >= MATE_SCORE - MAX_PLY as i32
+}
+
+struct Search<'a, F> {
+    limits: SearchLimits,
+    table: &'a mut TranspositionTable,
+    now_ms: F,
+    deadline: Option<u64>,
+    nodes: u64,
+    // Node count at which the limits are next checked.
+    next_check: u64,
+    stopped: bool,
+    // The best line from the previous iteration, tried first at each ply.
+    previous_pv: Vec<Move>,
+    // Hashes of every position from the start of the game to the current node.
+    hashes: Vec<ZobristHash>,
+}
+
+impl<F: FnMut() -> u64> Search<'_, F> {
+    // Counts a node and reports whether the search has to stop. Every node goes
+    // through here so the node limit is never stepped over.
+    fn visit(&mut self) -> bool {
+        if self.stopped {
+            return true;
+        }
+        self.nodes += 1;
+        if self.nodes < self.next_check {
+            return false;
+        }
+
+        let out_of_nodes = self.limits.nodes.is_some_and(|limit| self.nodes >= limit);
+        let out_of_time = self
+            .deadline
+            .is_some_and(|deadline| (self.now_ms)() >= deadline);
+        self.stopped = out_of_nodes || out_of_time;
+        self.next_check = self.nodes + LIMIT_CHECK_INTERVAL;
+        if let Some(limit) = self.limits.nodes {
+            self.next_check = self.next_check.min(limit);
+        }
+        self.stopped
+    }
+
+    // Repetitions only count back to the last capture or pawn move.
+    fn is_repetition(&self, state: &GameState) -> bool {
+        let current = self.hashes.len() - 1;
+        let window = (state.halfmove_clock as usize).min(current);
+        self.hashes[current - window..current]
+            .iter()
+            .rev()
+            .skip(1)
+            .step_by(2)
+            .any(|hash| *hash == self.hashes[current])
+    }
+
+    fn is_draw(&self, state: &GameState) -> bool {
+        state.halfmove_clock >= FIFTY_MOVE_RULE_PLIES
+            || has_insufficient_material(state)
+            || self.is_repetition(state)
+    }
+
+    fn play(&mut self, state: &mut GameState, mv: &Move) -> MoveUndo {
+        let undo = make_move(state, mv).expect("legal move");
+        let hash = hash_after_move(self.hashes[self.hashes.len() - 1], state, &undo);
+        self.hashes.push(hash);
+        undo
+    }
+
+    fn take_back(&mut self, state: &mut GameState, undo: MoveUndo) {
+        self.hashes.pop();
+        unmake_move(state, undo);
+    }
+
+    fn negamax(
+        &mut self,
+        state: &mut GameState,
+        depth: u32,
+        ply: usize,
+        mut alpha: i32,
+        beta: i32,
+        pv: &mut Vec<Move>,
+    ) -> i32 {
+        pv.clear();
+        if ply > 0 && self.is_draw(state) {
+            self.visit();
+            return 0;
+        }
+        if depth == 0 || ply >= MAX_PLY {
+            return self.quiescence(state, ply, alpha, beta);
+        }
+
+        if self.visit() {
+            return 0;
+        }
+
+        // Table cutoffs are only taken outside the principal variation, which
+        // would otherwise come back cut short.
+        let key = self.hashes[self.hashes.len() - 1];
+        let pv_node = beta - alpha > 1;
+        let entry = self.table.probe(key, ply);
+        if let Some(entry) = &entry {
+            if !pv_node && u32::from(entry.depth) >= depth {
+                match entry.bound {
+                    Bound::Exact => return entry.score,
+                    Bound::Lower if entry.score >= beta => return entry.score,
+                    Bound::Upper if entry.score <= alpha => return entry.score,
+                    _ => {}
+                }
+            }
+        }
+
+        let mut moves = generate_legal_moves(state);
+        if moves.is_empty() {
+            return if is_in_check_for_color(state, state.active_color) {
+                -(MATE_SCORE - ply as i32)
+            } else {
+                0
+            };
+        }
+
+        let table_move = entry.and_then(|entry| entry.best_move);
+        let hint = table_move.as_ref().or(self.previous_pv.get(ply));
+        order_moves(state, &mut moves, hint);
+
+        // Principal variation search: after the first move, each move is tried
+        // with a null window and only re-searched if it might raise alpha.
+        let original_alpha = alpha;
+        let mut child_pv = Vec::new();
+        for (index, mv) in moves.iter().enumerate() {
+            let undo = self.play(state, mv);
+            let score = if index == 0 {
+                -self.negamax(state, depth - 1, ply + 1, -beta, -alpha, &mut child_pv)
+            } else {
+                let score =
+                    -self.negamax(state, depth - 1, ply + 1, -alpha - 1, -alpha, &mut child_pv);
+                if score > alpha && score < beta {
+                    -self.negamax(state, depth - 1, ply + 1, -beta, -alpha, &mut child_pv)
+                } else {
+                    score
+                }
+            };
+            self.take_back(state, undo);
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(mv.clone());
+                pv.extend(child_pv.iter().cloned());
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+
+        let bound = if alpha >= beta {
+            Bound::Lower
+        } else if alpha > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        let best_move = match bound {
+            Bound::Upper => None,
+            _ => pv.first().cloned(),
+        };
+        self.table.store(key, depth, bound, alpha, ply, best_move);
+
+        alpha
+    }
+
+    // Resolves captures so the static evaluation is never taken mid-exchange.
+    // Every evasion is searched when in check, so mates are still found.
+    fn quiescence(&mut self, state: &mut GameState, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        if self.visit() {
+            return 0;
+        }
+
+        let in_check = is_in_check_for_color(state, state.active_color);
+        let mut moves = generate_legal_moves(state);
+        if moves.is_empty() {
+            return if in_check {
+                -(MATE_SCORE - ply as i32)
+            } else {
+                0
+            };
+        }
+
+        if !in_check {
+            let stand_pat = evaluate(state);
+            if stand_pat >= beta || ply >= MAX_PLY {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+            moves.retain(|mv| is_capture(state, mv) || mv.promotion == Some(PieceKind::Queen));
+        }
+
+        order_moves(state, &mut moves, None);
+        for mv in &moves {
+            let undo = self.play(state, mv);
+            let score = -self.quiescence(state, ply + 1, -beta, -alpha);
+            self.take_back(state, undo);
+
+            if self.stopped {
+                return 0;
+            }
+            if score > alpha {
+                alpha = score;
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+
+        alpha
+    }
+}
+
+// Hint move first, then captures by most valuable victim and least valuable
+// attacker, then promotions, then quiet moves.
+fn order_moves(state: &GameState, moves: &mut [Move], hint: Option<&Move>) {
+    moves.sort_by_cached_key(|mv| {
+        if hint == Some(mv) {
+            return i32::MIN;
+        }
+
+        let attacker = state.board[mv.from.rank as usize][mv.from.file as usize]
+            .map_or(0, |piece| piece_value(piece.kind));
+        let victim = match state.board[mv.to.rank as usize][mv.to.file as usize] {
+            Some(piece) => piece_value(piece.kind),
+            None if is_capture(state, mv) => piece_value(PieceKind::Pawn),
+            None => 0,
+        };
+        let promotion = mv.promotion.map_or(0, piece_value);
+
+        let score = if victim > 0 {
+            10_000 + victim * 10 - attacker / 10 + promotion
+        } else {
+            promotion
+        };
+        -score
+    });
+}
+
+// `history` holds the hashes of the positions before `state`, oldest first, so
+// repetitions of earlier game positions are scored as draws. `table` is kept
+// between calls so later searches start from what earlier ones learned.
+pub fn search_with_table(
+    state: &GameState,
+    history: &[ZobristHash],
+    limits: SearchLimits,
+    table: &mut TranspositionTable,
+    mut now_ms: impl FnMut() -> u64,
+) -> SearchResult {
+    let mut result = SearchResult {
+        best_move: None,
+        score: 0,
+        pv: Vec::new(),
+        depth: 0,
+        nodes: 0,
+    };
+    if state.status != GameStatus::InProgress {
+        return result;
+    }
+
+    let deadline = limits.time_ms.map(|time| now_ms() + time);
+    let mut hashes = history.to_vec();
+    hashes.push(hash(state));
+
+    table.new_search();
+    let mut search = Search {
+        limits,
+        table,
+        now_ms,
+        deadline,
+        nodes: 0,
+        next_check: limits.nodes.map_or(LIMIT_CHECK_INTERVAL, |limit| {
+            limit.min(LIMIT_CHECK_INTERVAL)
+        }),
+        stopped: false,
+        previous_pv: Vec::new(),
+        hashes,
+    };
+    let mut scratch = state.clone();
+    let max_depth = limits
+        .depth
+        .unwrap_or(MAX_PLY as u32)
+        .clamp(1, MAX_PLY as u32);
+
+    for depth in 1..=max_depth {
+        let mut pv = Vec::new();
+        let score = search.negamax(&mut scratch, depth, 0, -INFINITY, INFINITY, &mut pv);
+
+        // An interrupted iteration is discarded, unless it is the first and
+        // there is nothing else to go on.
+        if search.stopped {
+            if result.best_move.is_none() {
+                result.best_move = pv.first().cloned().or_else(|| {
+                    let mut moves = generate_legal_moves(state);
+                    order_moves(state, &mut moves, None);
+                    moves.first().cloned()
+                });
+                result.score = evaluate(state);
+                result.pv = result.best_move.iter().cloned().collect();
+            }
+            break;
+        }
+
+        result.best_move = pv.first().cloned();
+        result.score = score;
+        result.pv = pv;
+        result.depth = depth;
+        search.previous_pv = result.pv.clone();
+
+        // A mate inside the searched depth cannot be improved on.
+        if is_mate_score(score) && MATE_SCORE - score.abs() <= depth as i32 {
+            break;
+        }
+    }
+
+    result.nodes = search.nodes;
+    result
+}
+
+// Searches `state` with no knowledge of earlier positions, using a small table
+// that only lasts for this call. `now_ms` supplies timestamps for the time limit
+// and is not called when there is none.
+pub fn search(
+    state: &GameState,
+    limits: SearchLimits,
+    now_ms: impl FnMut() -> u64,
+) -> SearchResult {
+    let mut table = TranspositionTable::new(ONE_OFF_TABLE_MB);
+    search_with_table(state, &[], limits, &mut table, now_ms)
+}
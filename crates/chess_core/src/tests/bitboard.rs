@@ -0,0 +1,93 @@
+use crate::bitboard::{
+    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks, square_index,
+    squares, Bitboard, Position,
+};
+use crate::board::{PieceColor, PieceKind, Square};
+use crate::{from_fen, new_game};
+
+fn index(square: &str) -> usize {
+    square_index(Square::from_algebraic(square).expect("valid square"))
+}
+
+fn bits(names: &[&str]) -> Bitboard {
+    names.iter().fold(0, |acc, name| acc | 1 << index(name))
+}
+
+#[test]
+fn leaper_tables_respect_board_edges() {
+    assert_eq!(knight_attacks(index("a1")), bits(&["b3", "c2"]));
+    assert_eq!(knight_attacks(index("h8")), bits(&["g6", "f7"]));
+    assert_eq!(knight_attacks(index("d4")).count_ones(), 8);
+    assert_eq!(king_attacks(index("a1")), bits(&["a2", "b1", "b2"]));
+    assert_eq!(king_attacks(index("e4")).count_ones(), 8);
+    assert_eq!(pawn_attacks(PieceColor::White, index("a2")), bits(&["b3"]));
+    assert_eq!(
+        pawn_attacks(PieceColor::Black, index("e7")),
+        bits(&["d6", "f6"])
+    );
+}
+
+#[test]
+fn sliding_attacks_stop_at_the_first_blocker() {
+    let occupied = bits(&["d6", "b4", "d2"]);
+    assert_eq!(
+        rook_attacks(index("d4"), occupied),
+        bits(&["d5", "d6", "c4", "b4", "e4", "f4", "g4", "h4", "d3", "d2"])
+    );
+
+    let occupied = bits(&["f6", "b2"]);
+    assert_eq!(
+        bishop_attacks(index("d4"), occupied),
+        bits(&["e5", "f6", "c5", "b6", "a7", "e3", "f2", "g1", "c3", "b2"])
+    );
+}
+
+#[test]
+fn squares_iterates_from_a1_to_h8() {
+    let found: Vec<usize> = squares(bits(&["h8", "a1", "e4"])).collect();
+    assert_eq!(found, vec![index("a1"), index("e4"), index("h8")]);
+}
+
+#[test]
+fn position_round_trips_the_board() {
+    let state = from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+        .expect("valid fen");
+    let position = Position::from_board(&state.board);
+
+    assert_eq!(position.to_board(), state.board);
+    assert_eq!(position.occupied().count_ones(), 32);
+    assert_eq!(position.king_square(PieceColor::Black), Some(index("e8")));
+}
+
+#[test]
+fn put_and_remove_keep_color_sets_in_step() {
+    let mut position = Position::from_board(&new_game().board);
+    let pawn = position.remove(index("e2")).expect("pawn on e2");
+    position.put(index("e4"), pawn);
+
+    assert_eq!(pawn.kind, PieceKind::Pawn);
+    assert_eq!(position.piece_at(index("e2")), None);
+    assert_eq!(position.piece_at(index("e4")), Some(pawn));
+    assert_eq!(position.color(PieceColor::White).count_ones(), 16);
+    assert_eq!(
+        position.pieces(PieceColor::White, PieceKind::Pawn) & bits(&["e2", "e4"]),
+        bits(&["e4"])
+    );
+}
+
+#[test]
+fn attackers_to_finds_every_attacker() {
+    let state = from_fen("4k3/8/8/3q4/8/2N5/1B3P2/4K2R w K - 0 1").expect("valid fen");
+    let position = Position::from_board(&state.board);
+
+    assert_eq!(
+        position.attackers_to(index("e4"), PieceColor::White),
+        bits(&["c3"])
+    );
+    assert_eq!(
+        position.attackers_to(index("d5"), PieceColor::White),
+        bits(&["c3"])
+    );
+    assert!(position.is_attacked_by(index("g2"), PieceColor::Black));
+    assert!(!position.is_attacked_by(index("e1"), PieceColor::Black));
+}
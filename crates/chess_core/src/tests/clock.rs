@@ -0,0 +1,138 @@
+use crate::board::PieceColor;
+use crate::clock::{
+    timeout_status, ClockError, GameClock, TimeBonus, TimeControl, TimeControlStage,
+};
+use crate::game::{DrawReason, GameResult, GameStatus, Termination};
+use crate::{from_fen, ChessEngine};
+
+const SECOND: u64 = 1_000;
+const MINUTE: u64 = 60 * SECOND;
+
+fn started(time_control: TimeControl) -> GameClock {
+    let mut clock = GameClock::new(time_control).expect("time control has stages");
+    clock.start(PieceColor::White, 0).expect("clock starts");
+    clock
+}
+
+#[test]
+fn fischer_increment_is_added_after_each_move() {
+    let mut clock = started(TimeControl::fischer(3 * MINUTE, 2 * SECOND));
+
+    assert_eq!(clock.remaining(PieceColor::White, 5 * SECOND), 175 * SECOND);
+    clock.press(5 * SECOND).expect("white moves in time");
+    assert_eq!(clock.remaining(PieceColor::White, 5 * SECOND), 177 * SECOND);
+    assert_eq!(clock.active_color(), Some(PieceColor::Black));
+
+    clock.press(6 * SECOND).expect("black moves in time");
+    assert_eq!(clock.remaining(PieceColor::Black, 6 * SECOND), 181 * SECOND);
+}
+
+#[test]
+fn bronstein_delay_refunds_up_to_the_delay() {
+    let mut clock = started(TimeControl::bronstein(MINUTE, 3 * SECOND));
+    clock.press(2 * SECOND).expect("white moves in time");
+    assert_eq!(clock.remaining(PieceColor::White, 2 * SECOND), MINUTE);
+
+    clock.press(12 * SECOND).expect("black moves in time");
+    assert_eq!(clock.remaining(PieceColor::Black, 12 * SECOND), 53 * SECOND);
+}
+
+#[test]
+fn simple_delay_holds_the_clock_during_the_delay() {
+    let mut clock = started(TimeControl::simple_delay(MINUTE, 5 * SECOND));
+
+    assert_eq!(clock.remaining(PieceColor::White, 4 * SECOND), MINUTE);
+    assert_eq!(clock.remaining(PieceColor::White, 8 * SECOND), 57 * SECOND);
+    clock.press(8 * SECOND).expect("white moves in time");
+    assert_eq!(clock.remaining(PieceColor::White, 8 * SECOND), 57 * SECOND);
+}
+
+#[test]
+fn multi_stage_control_adds_time_after_the_move_quota() {
+    // 40/90+30 followed by 30+30, with a 30 second increment throughout.
+    let mut clock = started(TimeControl::new(vec![
+        TimeControlStage {
+            moves: Some(40),
+            base_ms: 90 * MINUTE,
+            bonus: TimeBonus::Increment(30 * SECOND),
+        },
+        TimeControlStage {
+            moves: None,
+            base_ms: 30 * MINUTE,
+            bonus: TimeBonus::Increment(30 * SECOND),
+        },
+    ]));
+
+    let mut now = 0;
+    for _ in 0..40 {
+        now += SECOND;
+        clock.press(now).expect("white moves in time");
+        clock.press(now).expect("black moves instantly");
+    }
+
+    // 90 min - 40 s used + 40 increments + 30 min second stage.
+    assert_eq!(
+        clock.remaining(PieceColor::White, now),
+        120 * MINUTE - 40 * SECOND + 40 * 30 * SECOND
+    );
+    assert_eq!(
+        clock.remaining(PieceColor::Black, now),
+        120 * MINUTE + 40 * 30 * SECOND
+    );
+}
+
+#[test]
+fn detects_flag_fall_and_rejects_late_moves() {
+    let mut clock = started(TimeControl::sudden_death(MINUTE));
+
+    assert_eq!(clock.flagged(MINUTE - 1), None);
+    assert_eq!(clock.flagged(MINUTE), Some(PieceColor::White));
+    assert_eq!(clock.remaining(PieceColor::White, 2 * MINUTE), 0);
+    assert_eq!(
+        clock.press(MINUTE + 1),
+        Err(ClockError::Flagged(PieceColor::White))
+    );
+    assert_eq!(clock.active_color(), None);
+}
+
+#[test]
+fn rejects_invalid_clock_operations() {
+    assert_eq!(
+        GameClock::new(TimeControl::new(Vec::new())),
+        Err(ClockError::NoStages)
+    );
+
+    let mut clock = GameClock::new(TimeControl::sudden_death(MINUTE)).expect("has stages");
+    assert_eq!(clock.press(0), Err(ClockError::NotRunning));
+    clock.start(PieceColor::White, 10).expect("clock starts");
+    assert_eq!(
+        clock.start(PieceColor::White, 10),
+        Err(ClockError::AlreadyRunning)
+    );
+    assert_eq!(clock.press(5), Err(ClockError::TimeWentBackwards));
+
+    clock.stop(20).expect("clock running");
+    assert_eq!(clock.remaining(PieceColor::White, 1_000), MINUTE - 10);
+}
+
+#[test]
+fn timeout_respects_insufficient_material_for_win() {
+    let state = from_fen("4k3/8/8/8/8/8/8/Q3K3 w - - 0 1").expect("valid fen");
+    assert_eq!(
+        timeout_status(&state, PieceColor::Black),
+        GameStatus::Timeout(PieceColor::Black)
+    );
+    assert_eq!(
+        timeout_status(&state, PieceColor::White),
+        GameStatus::Draw(DrawReason::TimeoutVsInsufficientMaterial)
+    );
+
+    let mut engine = ChessEngine::from_fen("4k3/8/8/8/8/8/8/Q3K3 w - - 0 1").expect("valid fen");
+    assert_eq!(
+        engine.timeout(PieceColor::Black),
+        Ok(GameResult {
+            winner: Some(PieceColor::White),
+            termination: Termination::Timeout,
+        })
+    );
+}
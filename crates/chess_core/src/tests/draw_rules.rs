@@ -0,0 +1,120 @@
+use crate::board::PieceColor;
+use crate::game::{DrawReason, GameStatus, MoveInput};
+use crate::rules::{can_claim_fifty_move_rule, can_win_on_time, has_insufficient_material};
+use crate::{apply_move, from_fen, ChessEngine};
+
+fn uci(text: &str) -> MoveInput {
+    text.parse().expect("valid uci")
+}
+
+#[test]
+fn fifty_move_rule_is_claimable_but_not_automatic() {
+    let state = from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").expect("valid fen");
+    assert!(!can_claim_fifty_move_rule(&state));
+
+    let next = apply_move(&state, uci("a1a2")).expect("legal move").state;
+    assert_eq!(next.halfmove_clock, 100);
+    assert_eq!(next.status, GameStatus::InProgress);
+    assert!(can_claim_fifty_move_rule(&next));
+
+    let mut engine = ChessEngine::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").expect("valid fen");
+    engine.apply_move(uci("a1a2")).expect("legal move");
+    assert!(engine.can_claim_fifty_move_rule());
+}
+
+#[test]
+fn capture_or_pawn_move_resets_the_count() {
+    let state = from_fen("4k3/8/8/8/8/8/P7/R3K3 w - - 120 80").expect("valid fen");
+    let next = apply_move(&state, uci("a2a3")).expect("legal move").state;
+
+    assert_eq!(next.halfmove_clock, 0);
+    assert!(!can_claim_fifty_move_rule(&next));
+}
+
+#[test]
+fn seventy_five_move_rule_draws_automatically() {
+    let state = from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 149 100").expect("valid fen");
+    let next = apply_move(&state, uci("a1a2")).expect("legal move").state;
+
+    assert_eq!(
+        next.status,
+        GameStatus::Draw(DrawReason::SeventyFiveMoveRule)
+    );
+    assert!(!can_claim_fifty_move_rule(&next));
+    assert!(apply_move(&next, uci("e8e7")).is_err());
+}
+
+#[test]
+fn checkmate_on_the_seventy_fifth_move_still_wins() {
+    let state = from_fen("k7/8/1K6/8/8/8/8/7R w - - 149 100").expect("valid fen");
+    let next = apply_move(&state, uci("h1h8")).expect("legal move").state;
+
+    assert_eq!(next.halfmove_clock, 150);
+    assert_eq!(next.status, GameStatus::Checkmate(PieceColor::White));
+}
+
+#[test]
+fn detects_fide_insufficient_material() {
+    let drawn = [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1N2K3 w - - 0 1",
+        "2b1k3/8/8/8/8/8/8/3BK3 w - - 0 1",
+        "4k3/8/8/2b1b3/8/8/8/B3K3 w - - 0 1",
+    ];
+    for fen in drawn {
+        let state = from_fen(fen).expect("valid fen");
+        assert!(has_insufficient_material(&state), "{fen}");
+        assert_eq!(
+            state.status,
+            GameStatus::Draw(DrawReason::InsufficientMaterial),
+            "{fen}"
+        );
+    }
+
+    let playable = [
+        "4k3/8/8/8/8/8/8/2BBK3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1NB1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/NN2K3 w - - 0 1",
+        "3bk3/8/8/8/8/8/8/3BK3 w - - 0 1",
+        "4kn2/8/8/8/8/8/8/1N2K3 w - - 0 1",
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+    ];
+    for fen in playable {
+        let state = from_fen(fen).expect("valid fen");
+        assert!(!has_insufficient_material(&state), "{fen}");
+        assert_eq!(state.status, GameStatus::InProgress, "{fen}");
+    }
+}
+
+#[test]
+fn capture_into_insufficient_material_ends_the_game() {
+    let state = from_fen("4k3/8/8/8/8/8/3r4/3QK3 w - - 0 1").expect("valid fen");
+    let next = apply_move(&state, uci("d1d2")).expect("legal move").state;
+    assert!(!has_insufficient_material(&next));
+
+    let state = from_fen("4k3/8/8/8/8/8/3r4/3NK3 w - - 0 1").expect("valid fen");
+    let next = apply_move(&state, uci("e1d2")).expect("legal move").state;
+    assert_eq!(
+        next.status,
+        GameStatus::Draw(DrawReason::InsufficientMaterial)
+    );
+}
+
+#[test]
+fn cannot_win_on_time_without_mating_material() {
+    // A lone king never wins on time.
+    let state = from_fen("4k3/8/8/8/8/8/8/Q3K3 w - - 0 1").expect("valid fen");
+    assert!(can_win_on_time(&state, PieceColor::White));
+    assert!(!can_win_on_time(&state, PieceColor::Black));
+
+    // A knight can mate a king hemmed in by its own rook, but not a bare queen.
+    let state = from_fen("4k2r/8/8/8/8/8/8/1N2K3 w - - 0 1").expect("valid fen");
+    assert!(can_win_on_time(&state, PieceColor::White));
+    let state = from_fen("4k2q/8/8/8/8/8/8/1N2K3 w - - 0 1").expect("valid fen");
+    assert!(!can_win_on_time(&state, PieceColor::White));
+
+    // A bishop can help mate when opposing pawns can block.
+    let state = from_fen("4k3/4p3/8/8/8/8/8/2B1K3 w - - 0 1").expect("valid fen");
+    assert!(can_win_on_time(&state, PieceColor::White));
+}
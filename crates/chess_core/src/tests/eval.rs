@@ -0,0 +1,142 @@
+use crate::board::Piece;
+use crate::eval::{evaluate, evaluate_breakdown, MAX_PHASE};
+use crate::move_gen::opposite;
+use crate::{from_fen, new_game, GameState};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn state(fen: &str) -> GameState {
+    from_fen(fen).expect("valid fen")
+}
+
+// The same position with the board flipped and the colors swapped.
+fn mirrored(state: &GameState) -> GameState {
+    let mut mirror = state.clone();
+    for rank in 0..8 {
+        for file in 0..8 {
+            mirror.board[rank][file] = state.board[7 - rank][file].map(|piece| Piece {
+                color: opposite(piece.color),
+                kind: piece.kind,
+            });
+        }
+    }
+    mirror.active_color = opposite(state.active_color);
+    mirror
+}
+
+#[test]
+fn start_position_is_balanced() {
+    let breakdown = evaluate_breakdown(&new_game());
+    assert_eq!(breakdown.phase, MAX_PHASE);
+    assert_eq!(breakdown.white_score(), 0);
+    assert_eq!(breakdown.total, 0);
+}
+
+#[test]
+fn score_is_from_the_side_to_move() {
+    let white_to_move = state("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+    let black_to_move = state("4k3/8/8/8/8/8/8/R3K3 b - - 0 1");
+    assert!(evaluate(&white_to_move) > 400);
+    assert_eq!(evaluate(&black_to_move), -evaluate(&white_to_move));
+}
+
+#[test]
+fn mirrored_positions_score_the_same() {
+    for fen in [
+        KIWIPETE,
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ] {
+        let original = state(fen);
+        assert_eq!(evaluate(&original), evaluate(&mirrored(&original)), "{fen}");
+    }
+}
+
+#[test]
+fn total_is_the_tapered_sum_of_the_terms() {
+    let breakdown = evaluate_breakdown(&state(KIWIPETE));
+    let terms = [
+        breakdown.material,
+        breakdown.piece_squares,
+        breakdown.mobility,
+        breakdown.pawn_structure,
+        breakdown.king_safety,
+        breakdown.bishop_pair,
+    ];
+    let sum: i32 = terms.iter().map(|term| term.tapered(breakdown.phase)).sum();
+    assert_eq!(breakdown.total, sum);
+}
+
+#[test]
+fn phase_falls_as_pieces_come_off() {
+    assert_eq!(
+        evaluate_breakdown(&state("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1")).phase,
+        0
+    );
+    assert_eq!(
+        evaluate_breakdown(&state("3qk3/8/8/8/8/8/8/3QK3 w - - 0 1")).phase,
+        8
+    );
+}
+
+#[test]
+fn doubled_and_isolated_pawns_are_penalised() {
+    let healthy = evaluate_breakdown(&state("4k3/8/8/8/8/8/5PPP/4K3 w - - 0 1"));
+    let doubled = evaluate_breakdown(&state("4k3/8/8/8/8/6P1/6PP/4K3 w - - 0 1"));
+    let isolated = evaluate_breakdown(&state("4k3/8/8/8/8/8/P3P2P/4K3 w - - 0 1"));
+
+    assert!(doubled.pawn_structure.eg < healthy.pawn_structure.eg);
+    assert!(isolated.pawn_structure.mg < healthy.pawn_structure.mg);
+}
+
+#[test]
+fn passed_pawns_gain_value_as_they_advance() {
+    let back = evaluate_breakdown(&state("4k3/8/8/8/8/8/P7/4K3 w - - 0 1"));
+    let middle = evaluate_breakdown(&state("4k3/8/8/P7/8/8/8/4K3 w - - 0 1"));
+    let far = evaluate_breakdown(&state("4k3/P7/8/8/8/8/8/4K3 w - - 0 1"));
+    let blocked = evaluate_breakdown(&state("4k3/1p6/8/P7/8/8/8/4K3 w - - 0 1"));
+
+    assert!(back.pawn_structure.eg < middle.pawn_structure.eg);
+    assert!(middle.pawn_structure.eg < far.pawn_structure.eg);
+    // Black's pawn on b7 guards a6, so white's a5 pawn is not passed.
+    assert!(blocked.pawn_structure.eg < middle.pawn_structure.eg);
+}
+
+#[test]
+fn bishop_pair_is_rewarded() {
+    let pair = evaluate_breakdown(&state("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1"));
+    let bishop_and_knight = evaluate_breakdown(&state("4k3/8/8/8/8/8/8/2B1KN2 w - - 0 1"));
+    assert!(pair.bishop_pair.mg > 0);
+    assert_eq!(bishop_and_knight.bishop_pair.mg, 0);
+}
+
+#[test]
+fn sheltered_king_is_safer_than_an_exposed_one() {
+    let castled = evaluate_breakdown(&state(
+        "r1bq1rk1/pppp1ppp/2n2n2/2b1p3/2B1P3/2N2N2/PPPP1PPP/R1BQ1RK1 w - - 0 1",
+    ));
+    let exposed = evaluate_breakdown(&state(
+        "r1bq1rk1/pppp1ppp/2n2n2/2b1p3/2B1P3/2N2N2/PPPP4/R1BQ1RK1 w - - 0 1",
+    ));
+    assert!(castled.king_safety.mg > exposed.king_safety.mg);
+    assert_eq!(castled.king_safety.eg, 0);
+}
+
+#[test]
+fn kings_centralise_in_the_endgame() {
+    let corner = state("7k/8/8/8/8/8/8/K7 w - - 0 1");
+    let centre = state("7k/8/8/8/3K4/8/8/8 w - - 0 1");
+    assert!(evaluate(&centre) > evaluate(&corner));
+}
+
+#[test]
+fn developed_pieces_score_higher_than_undeveloped_ones() {
+    let undeveloped = state("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1");
+    let developed = state("rnbqkbnr/pppppppp/8/8/8/5N2/PPPPPPPP/RNBQKB1R b KQkq - 1 1");
+    let breakdown = evaluate_breakdown(&developed);
+
+    assert!(evaluate(&developed) < evaluate(&undeveloped));
+    assert!(breakdown.mobility.mg > 0);
+    assert!(breakdown.piece_squares.mg > 0);
+}
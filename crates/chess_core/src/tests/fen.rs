@@ -0,0 +1,248 @@
+use crate::board::{Piece, PieceColor, PieceKind, Square};
+use crate::fen::{FenError, STANDARD_START_FEN};
+use crate::game::{EngineError, GameState, GameStatus, MoveInput};
+use crate::{apply_move, from_fen, new_game, to_fen};
+
+fn piece_on(state: &GameState, square: &str) -> Option<Piece> {
+    let square = Square::from_algebraic(square).expect("valid square");
+    state.board[square.rank as usize][square.file as usize]
+}
+
+#[test]
+fn parses_standard_start_position() {
+    let state = from_fen(STANDARD_START_FEN).expect("start position should parse");
+    assert_eq!(state, new_game());
+}
+
+#[test]
+fn parses_all_six_fields_of_arbitrary_position() {
+    let state = from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b Kq - 3 17")
+        .expect("position should parse");
+
+    assert_eq!(state.active_color, PieceColor::Black);
+    assert!(state.castling_rights.white_king_side);
+    assert!(!state.castling_rights.white_queen_side);
+    assert!(!state.castling_rights.black_king_side);
+    assert!(state.castling_rights.black_queen_side);
+    assert_eq!(state.en_passant_target, None);
+    assert_eq!(state.halfmove_clock, 3);
+    assert_eq!(state.fullmove_number, 17);
+    assert_eq!(
+        piece_on(&state, "e7"),
+        Some(Piece {
+            color: PieceColor::Black,
+            kind: PieceKind::Queen
+        })
+    );
+    assert_eq!(
+        piece_on(&state, "f3"),
+        Some(Piece {
+            color: PieceColor::White,
+            kind: PieceKind::Queen
+        })
+    );
+    assert!(piece_on(&state, "e4").is_some());
+    assert!(piece_on(&state, "e5").is_some_and(|p| p.kind == PieceKind::Knight));
+}
+
+#[test]
+fn evaluates_status_of_loaded_position() {
+    let mate = from_fen("7k/6Q1/5K2/8/8/8/8/8 b - - 0 1").expect("mate position should parse");
+    assert_eq!(mate.status, GameStatus::Checkmate(PieceColor::White));
+
+    let stalemate = from_fen("7k/5K2/6Q1/8/8/8/8/8 b - - 0 1").expect("stalemate should parse");
+    assert_eq!(stalemate.status, GameStatus::Stalemate(PieceColor::Black));
+}
+
+#[test]
+fn rejects_malformed_fen_with_specific_error() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+    let cases = [
+        ("".to_string(), FenError::WrongFieldCount { found: 0 }),
+        (
+            format!("{start} w KQkq -"),
+            FenError::WrongFieldCount { found: 4 },
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1".to_string(),
+            FenError::WrongRankCount { found: 7 },
+        ),
+        (
+            "rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+            FenError::InvalidPiece {
+                rank: 6,
+                character: '9',
+            },
+        ),
+        (
+            "rnbqkbnr/ppppxppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+            FenError::InvalidPiece {
+                rank: 7,
+                character: 'x',
+            },
+        ),
+        (
+            "rnbqkbnr/ppppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+            FenError::RankOverflow { rank: 7 },
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/7/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+            FenError::RankUnderflow { rank: 3 },
+        ),
+        (
+            format!("{start} x KQkq - 0 1"),
+            FenError::InvalidSideToMove {
+                value: "x".to_string(),
+            },
+        ),
+        (
+            format!("{start} w KQkX - 0 1"),
+            FenError::InvalidCastling { character: 'X' },
+        ),
+        (
+            format!("{start} w KKkq - 0 1"),
+            FenError::InvalidCastling { character: 'K' },
+        ),
+        (
+            format!("{start} w KQkq e4 0 1"),
+            FenError::InvalidEnPassant {
+                value: "e4".to_string(),
+            },
+        ),
+        (
+            format!("{start} w KQkq - x 1"),
+            FenError::InvalidClock {
+                field: 4,
+                value: "x".to_string(),
+            },
+        ),
+        (
+            format!("{start} w KQkq - 0 0"),
+            FenError::InvalidClock {
+                field: 5,
+                value: "0".to_string(),
+            },
+        ),
+    ];
+
+    for (fen, expected) in cases {
+        assert_eq!(
+            from_fen(&fen),
+            Err(EngineError::InvalidFen(expected)),
+            "{fen}"
+        );
+    }
+}
+
+#[test]
+fn fen_error_reports_field_and_character() {
+    let Err(EngineError::InvalidFen(err)) =
+        from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkZ - 0 1")
+    else {
+        panic!("expected fen error");
+    };
+
+    assert_eq!(err.field(), 2);
+    assert_eq!(err.character(), Some('Z'));
+    assert_eq!(
+        EngineError::InvalidFen(err).to_string(),
+        "invalid fen: invalid castling character 'Z'"
+    );
+}
+
+#[test]
+fn clock_errors_name_the_field() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+    let message = |fen: String| match from_fen(&fen) {
+        Err(EngineError::InvalidFen(err)) => err.to_string(),
+        other => panic!("expected fen error, got {other:?}"),
+    };
+
+    assert_eq!(
+        message(format!("{start} w KQkq - x 1")),
+        "halfmove clock must be a valid move counter, found 'x'"
+    );
+    assert_eq!(
+        message(format!("{start} w KQkq - 0 0")),
+        "fullmove number must be a valid move counter, found '0'"
+    );
+}
+
+fn play(moves: &[(&str, &str)]) -> Vec<GameState> {
+    let mut state = new_game();
+    let mut positions = vec![state.clone()];
+
+    for (from, to) in moves {
+        let input = MoveInput {
+            from: Square::from_algebraic(from).expect("valid square"),
+            to: Square::from_algebraic(to).expect("valid square"),
+            promotion: None,
+        };
+        state = apply_move(&state, input).expect("legal move").state;
+        positions.push(state.clone());
+    }
+
+    positions
+}
+
+#[test]
+fn serializes_start_position() {
+    assert_eq!(to_fen(&new_game()), STANDARD_START_FEN);
+}
+
+#[test]
+fn serializes_position_after_moves() {
+    let positions = play(&[("e2", "e4"), ("c7", "c5"), ("g1", "f3")]);
+
+    assert_eq!(
+        to_fen(&positions[1]),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+    );
+    assert_eq!(
+        to_fen(&positions[3]),
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+    );
+}
+
+#[test]
+fn round_trips_positions_produced_by_apply_move() {
+    // Covers castling on both sides, en passant, a promotion and lost castling rights.
+    let games: [&[(&str, &str)]; 3] = [
+        &[
+            ("e2", "e4"),
+            ("e7", "e5"),
+            ("g1", "f3"),
+            ("b8", "c6"),
+            ("f1", "c4"),
+            ("g8", "f6"),
+            ("e1", "g1"),
+            ("d7", "d6"),
+            ("d2", "d3"),
+            ("c8", "g4"),
+            ("b1", "c3"),
+            ("d8", "d7"),
+            ("c1", "e3"),
+            ("e8", "c8"),
+        ],
+        &[
+            ("e2", "e4"),
+            ("a7", "a6"),
+            ("e4", "e5"),
+            ("d7", "d5"),
+            ("e5", "d6"),
+            ("h7", "h6"),
+            ("d6", "c7"),
+            ("h6", "h5"),
+            ("c7", "b8"),
+        ],
+        &[("a2", "a4"), ("h7", "h5"), ("a1", "a3"), ("h8", "h6")],
+    ];
+
+    for game in games {
+        for state in play(game) {
+            let fen = to_fen(&state);
+            let parsed = from_fen(&fen).expect("serialized fen should parse");
+            assert_eq!(parsed, state, "{fen}");
+        }
+    }
+}
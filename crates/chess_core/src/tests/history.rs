@@ -0,0 +1,84 @@
+use crate::game::{EngineError, MoveInput};
+use crate::{new_game, to_fen, ChessEngine};
+
+fn play(engine: &mut ChessEngine, moves: &[&str]) {
+    for uci in moves {
+        let input: MoveInput = uci.parse().expect("valid uci");
+        engine.apply_move(input).expect("legal move");
+    }
+}
+
+#[test]
+fn records_every_ply_in_history() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4", "e7e5", "g1f3"]);
+
+    let moves: Vec<String> = engine
+        .history()
+        .iter()
+        .map(|result| result.move_applied.to_uci())
+        .collect();
+    assert_eq!(moves, ["e2e4", "e7e5", "g1f3"]);
+    assert_eq!(engine.ply(), 3);
+    assert_eq!(engine.state(), &engine.history()[2].state);
+}
+
+#[test]
+fn undo_and_redo_move_through_history() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4", "e7e5"]);
+    let after_e4 = engine.history()[0].state.clone();
+
+    let undone = engine.undo().expect("a ply to undo").move_applied.to_uci();
+    assert_eq!(undone, "e7e5");
+    assert_eq!(engine.state(), &after_e4);
+
+    engine.undo().expect("a ply to undo");
+    assert_eq!(engine.state(), &new_game());
+    assert!(engine.undo().is_none());
+
+    let redone = engine.redo().expect("a ply to redo").move_applied.to_uci();
+    assert_eq!(redone, "e2e4");
+    assert_eq!(engine.state(), &after_e4);
+
+    engine.redo().expect("a ply to redo");
+    assert!(engine.redo().is_none());
+    assert_eq!(engine.ply(), 2);
+}
+
+#[test]
+fn new_move_discards_redo_branch() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4", "e7e5", "g1f3"]);
+
+    engine.undo();
+    engine.undo();
+    play(&mut engine, &["c7c5"]);
+
+    assert_eq!(engine.history().len(), 2);
+    assert!(engine.redo().is_none());
+    assert_eq!(
+        to_fen(engine.state()),
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2"
+    );
+}
+
+#[test]
+fn goto_ply_jumps_without_losing_history() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4", "e7e5", "g1f3", "b8c6"]);
+
+    engine.goto_ply(1).expect("ply in range");
+    assert_eq!(engine.state(), &engine.history()[0].state);
+    assert_eq!(engine.history().len(), 4);
+    assert!(engine.to_pgn().ends_with("1. e4 *\n"));
+
+    engine.goto_ply(0).expect("ply in range");
+    assert_eq!(engine.state(), &new_game());
+
+    engine.goto_ply(4).expect("ply in range");
+    assert_eq!(engine.ply(), 4);
+
+    assert_eq!(engine.goto_ply(5), Err(EngineError::PlyOutOfRange(5)));
+    assert_eq!(engine.ply(), 4);
+}
@@ -2,7 +2,10 @@ use std::collections::BTreeSet;
 
 use crate::board::{empty_board, Piece, PieceColor, PieceKind, Square};
 use crate::game::{GameState, GameStatus, MoveInput};
-use crate::{apply_move, legal_moves, new_game, CastlingRights};
+use crate::{
+    apply_move, legal_captures, legal_checks, legal_moves, legal_moves_all, legal_quiet_moves,
+    new_game, CastlingRights,
+};
 
 fn custom_state(active_color: PieceColor) -> GameState {
     GameState {
@@ -151,3 +154,66 @@ fn illegal_move_is_rejected_when_in_check_and_not_resolving() {
 
     assert!(result.is_err());
 }
+
+fn uci_set(moves: &[crate::Move]) -> BTreeSet<String> {
+    moves.iter().map(|m| m.to_uci()).collect()
+}
+
+#[test]
+fn start_position_has_twenty_legal_moves() {
+    let state = new_game();
+    let moves = legal_moves_all(&state);
+
+    assert_eq!(moves.len(), 20);
+    assert!(legal_captures(&state).is_empty());
+    assert!(legal_checks(&state).is_empty());
+    assert_eq!(legal_quiet_moves(&state).len(), 20);
+}
+
+#[test]
+fn full_position_generation_respects_check() {
+    let mut state = custom_state(PieceColor::White);
+    set_piece(&mut state, "e1", PieceColor::White, PieceKind::King);
+    set_piece(&mut state, "a1", PieceColor::White, PieceKind::Rook);
+    set_piece(&mut state, "e8", PieceColor::Black, PieceKind::Rook);
+    set_piece(&mut state, "h8", PieceColor::Black, PieceKind::King);
+
+    let moves = uci_set(&legal_moves_all(&state));
+    let expected: BTreeSet<String> = ["e1d1", "e1d2", "e1f1", "e1f2"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    assert_eq!(moves, expected);
+}
+
+#[test]
+fn filters_captures_checks_and_quiet_moves() {
+    let mut state = custom_state(PieceColor::White);
+    set_piece(&mut state, "a1", PieceColor::White, PieceKind::King);
+    set_piece(&mut state, "h8", PieceColor::Black, PieceKind::King);
+    set_piece(&mut state, "d4", PieceColor::White, PieceKind::Rook);
+    set_piece(&mut state, "d7", PieceColor::Black, PieceKind::Knight);
+    set_piece(&mut state, "e5", PieceColor::White, PieceKind::Pawn);
+    set_piece(&mut state, "f5", PieceColor::Black, PieceKind::Pawn);
+    set_piece(&mut state, "b7", PieceColor::White, PieceKind::Pawn);
+    state.en_passant_target = Some(Square::from_algebraic("f6").expect("valid square"));
+
+    let all = legal_moves_all(&state);
+    let captures = uci_set(&legal_captures(&state));
+    let checks = uci_set(&legal_checks(&state));
+    let quiet = legal_quiet_moves(&state);
+
+    assert_eq!(
+        captures,
+        ["d4d7", "e5f6"].into_iter().map(String::from).collect()
+    );
+    assert_eq!(
+        checks,
+        ["b7b8q", "b7b8r", "d4h4"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    );
+    assert!(quiet.iter().all(|mv| mv.promotion.is_none()));
+    assert_eq!(quiet.len() + captures.len() + 4, all.len());
+}
@@ -0,0 +1,97 @@
+use crate::board::{Piece, PieceColor, PieceKind, Square};
+use crate::game::{GameState, Move};
+use crate::move_gen::{generate_legal_moves, make_move, unmake_move};
+use crate::{from_fen, new_game};
+
+fn square(name: &str) -> Square {
+    Square::from_algebraic(name).expect("valid square")
+}
+
+fn piece_on(state: &GameState, name: &str) -> Option<Piece> {
+    let square = square(name);
+    state.board[square.rank as usize][square.file as usize]
+}
+
+fn mv(from: &str, to: &str, promotion: Option<PieceKind>) -> Move {
+    Move {
+        from: square(from),
+        to: square(to),
+        promotion,
+    }
+}
+
+fn assert_round_trips(fen: &str) {
+    let original = from_fen(fen).expect("valid fen");
+    let mut state = original.clone();
+
+    for mv in generate_legal_moves(&original) {
+        let undo = make_move(&mut state, &mv).expect("legal move");
+        assert_ne!(state, original, "{mv} changed nothing");
+        unmake_move(&mut state, undo);
+        assert_eq!(state, original, "{mv} did not unmake cleanly in {fen}");
+    }
+}
+
+#[test]
+fn unmake_restores_every_move_in_tricky_positions() {
+    assert_round_trips("startpos");
+    assert_round_trips("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+    assert_round_trips("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8");
+    assert_round_trips("8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1");
+}
+
+#[test]
+fn castling_moves_the_rook_and_clears_rights() {
+    let mut state = from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 3 10").expect("valid fen");
+    make_move(&mut state, &mv("e1", "c1", None)).expect("legal move");
+
+    assert_eq!(piece_on(&state, "a1"), None);
+    assert_eq!(
+        piece_on(&state, "d1").map(|piece| piece.kind),
+        Some(PieceKind::Rook)
+    );
+    assert!(!state.castling_rights.white_king_side);
+    assert!(!state.castling_rights.white_queen_side);
+    assert!(state.castling_rights.black_king_side);
+    assert_eq!(state.halfmove_clock, 4);
+    assert_eq!(state.active_color, PieceColor::Black);
+}
+
+#[test]
+fn en_passant_removes_the_passed_pawn() {
+    let mut state = from_fen("8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1").expect("valid fen");
+    let undo = make_move(&mut state, &mv("e4", "d3", None)).expect("legal move");
+
+    assert_eq!(undo.captured.map(|(square, _)| square), Some(square("d4")));
+    assert_eq!(piece_on(&state, "d4"), None);
+    assert_eq!(state.en_passant_target, None);
+    assert_eq!(state.fullmove_number, 2);
+}
+
+#[test]
+fn promotion_capture_records_the_captured_piece() {
+    let mut state =
+        from_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8").expect("valid fen");
+    let undo = make_move(&mut state, &mv("d7", "c8", Some(PieceKind::Knight))).expect("legal move");
+
+    assert_eq!(
+        undo.captured.map(|(_, piece)| piece.kind),
+        Some(PieceKind::Bishop)
+    );
+    assert_eq!(
+        piece_on(&state, "c8"),
+        Some(Piece {
+            color: PieceColor::White,
+            kind: PieceKind::Knight
+        })
+    );
+    assert_eq!(state.halfmove_clock, 0);
+}
+
+#[test]
+fn double_push_sets_the_en_passant_target() {
+    let mut state = new_game();
+    make_move(&mut state, &mv("e2", "e4", None)).expect("legal move");
+
+    assert_eq!(state.en_passant_target, Some(square("e3")));
+}
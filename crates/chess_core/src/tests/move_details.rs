@@ -0,0 +1,82 @@
+use crate::board::{Piece, PieceColor, PieceKind, Square};
+use crate::game::{MoveFlags, MoveInput, MoveResult};
+use crate::{apply_move, from_fen};
+
+fn square(name: &str) -> Square {
+    Square::from_algebraic(name).expect("valid square")
+}
+
+fn play(fen: &str, uci: &str) -> MoveResult {
+    let state = from_fen(fen).expect("valid fen");
+    apply_move(&state, MoveInput::from_uci(uci).expect("valid uci")).expect("legal move")
+}
+
+#[test]
+fn quiet_double_push() {
+    let details = play("startpos", "e2e4").details;
+
+    assert_eq!(
+        details.moved_piece,
+        Piece {
+            color: PieceColor::White,
+            kind: PieceKind::Pawn
+        }
+    );
+    assert_eq!(details.captured_piece, None);
+    assert_eq!(
+        details.flags,
+        MoveFlags {
+            double_push: true,
+            ..MoveFlags::default()
+        }
+    );
+    assert!(!details.gives_check);
+}
+
+#[test]
+fn en_passant_reports_the_passed_pawn_square() {
+    let details = play("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2", "e5d6").details;
+
+    assert!(details.flags.capture && details.flags.en_passant);
+    assert_eq!(details.captured_square, Some(square("d5")));
+    assert_eq!(
+        details.captured_piece.map(|piece| piece.kind),
+        Some(PieceKind::Pawn)
+    );
+}
+
+#[test]
+fn castling_reports_the_rook_path() {
+    let details = play("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", "e8c8").details;
+
+    assert!(details.flags.castling);
+    assert_eq!(details.castle_rook_from, Some(square("a8")));
+    assert_eq!(details.castle_rook_to, Some(square("d8")));
+    assert_eq!(details.moved_piece.kind, PieceKind::King);
+}
+
+#[test]
+fn promotion_capture_with_check() {
+    let details = play("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7b8q").details;
+
+    assert!(details.flags.promotion && details.flags.capture);
+    assert_eq!(details.moved_piece.kind, PieceKind::Pawn);
+    assert_eq!(
+        details.captured_piece.map(|piece| piece.kind),
+        Some(PieceKind::Rook)
+    );
+    assert!(details.gives_check);
+    assert!(!details.gives_mate);
+}
+
+#[test]
+fn mate_sets_both_check_flags() {
+    let details = play(
+        "rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2",
+        "d8h4",
+    )
+    .details;
+
+    assert!(details.gives_check);
+    assert!(details.gives_mate);
+}
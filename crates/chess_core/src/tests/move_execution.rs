@@ -0,0 +1,81 @@
+use crate::bitboard::{square_index, Position};
+use crate::game::{GameState, GameStatus};
+use crate::move_gen::{
+    captured_by, generate_legal_moves, make_move, position_after, toggle_move, unmake_move,
+};
+use crate::{apply_move, from_fen, status};
+
+// Small deterministic generator so failures reproduce without a rand dependency.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+fn play_random_game(start: &GameState, rng: &mut XorShift, max_plies: usize) -> usize {
+    let mut state = start.clone();
+
+    for ply in 0..max_plies {
+        if state.status != GameStatus::InProgress {
+            return ply;
+        }
+        let moves = generate_legal_moves(&state);
+        let mv = moves[rng.next(moves.len())].clone();
+
+        let public = apply_move(&state, mv.clone().into()).expect("generated move is legal");
+
+        let mut made = state.clone();
+        let undo = make_move(&mut made, &mv).expect("legal move");
+        made.status = status(&made);
+        assert_eq!(public.state, made, "{mv} from {}", crate::to_fen(&state));
+
+        let before = Position::from_board(&state.board);
+        let position = position_after(&state, &before, &mv);
+        assert_eq!(position, Position::from_board(&made.board), "{mv}");
+
+        let piece = before
+            .piece_at(square_index(mv.from))
+            .expect("piece to move");
+        let captured = captured_by(&state, &before, piece, &mv);
+        let mut toggled = before;
+        toggle_move(&mut toggled, piece, &mv, captured);
+        assert_eq!(toggled, position, "{mv}");
+        toggle_move(&mut toggled, piece, &mv, captured);
+        assert_eq!(toggled, before, "{mv}");
+
+        let mut restored = made.clone();
+        restored.status = state.status.clone();
+        unmake_move(&mut restored, undo);
+        assert_eq!(restored, state, "{mv}");
+
+        state = public.state;
+    }
+
+    max_plies
+}
+
+#[test]
+fn every_execution_path_agrees_over_random_games() {
+    let starts = [
+        "startpos",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ];
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    let mut plies = 0;
+
+    for fen in starts {
+        let start = from_fen(fen).expect("valid fen");
+        for _ in 0..40 {
+            plies += play_random_game(&start, &mut rng, 200);
+        }
+    }
+
+    assert!(plies > 5_000, "only {plies} plies were played");
+}
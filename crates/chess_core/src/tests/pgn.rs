@@ -0,0 +1,172 @@
+use crate::board::PieceColor;
+use crate::game::GameStatus;
+use crate::pgn::{parse_pgn, PgnError, PgnResult};
+use crate::san::{parse_san, SanError};
+use crate::{to_fen, ChessEngine};
+
+const SCHOLARS_MATE: &str = r#"[Event "Casual Game"]
+[Site "Berlin GER"]
+[Date "2024.01.01"]
+[Round "?"]
+[White "Alice \"The Rook\""]
+[Black "Bob"]
+[Result "1-0"]
+[ECO "C20"]
+
+1. e4 e5 2. Bc4 {Aiming at f7.} Nc6 3. Qh5 Nf6?? ; a blunder
+4. Qxf7# 1-0
+"#;
+
+#[test]
+fn parses_tags_and_movetext() {
+    let game = parse_pgn(SCHOLARS_MATE).expect("valid pgn");
+
+    assert_eq!(game.tags.len(), 8);
+    assert_eq!(game.tag("White"), Some("Alice \"The Rook\""));
+    assert_eq!(game.tag("ECO"), Some("C20"));
+    assert_eq!(game.moves.len(), 7);
+    assert_eq!(game.result, PgnResult::WhiteWins);
+    assert_eq!(game.state.status, GameStatus::Checkmate(PieceColor::White));
+}
+
+#[test]
+fn skips_nags_variations_and_glued_move_numbers() {
+    let pgn = "1.e4 $1 e5 (1...c5 2.Nf3 {Sicilian}) 2.Nf3 Nc6 3.Bb5 a6 *";
+    let game = parse_pgn(pgn).expect("valid pgn");
+    assert_eq!(game.moves.len(), 6);
+    assert_eq!(game.result, PgnResult::Unknown);
+    assert_eq!(
+        to_fen(&game.state),
+        "r1bqkbnr/1ppp1ppp/p1n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 0 4"
+    );
+}
+
+#[test]
+fn replays_from_fen_setup() {
+    let pgn = r#"[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"]
+
+1. e4 Kd7 2. e5 1/2-1/2"#;
+    let game = parse_pgn(pgn).expect("valid pgn");
+
+    assert_eq!(game.moves.len(), 3);
+    assert_eq!(game.result, PgnResult::Draw);
+    assert_eq!(to_fen(&game.state), "8/3k4/8/4P3/8/8/8/4K3 b - - 0 2");
+}
+
+#[test]
+fn reports_ply_and_token_of_illegal_move() {
+    let pgn = "1. e4 e5 2. Ke3 Nc6 *";
+
+    assert_eq!(
+        parse_pgn(pgn),
+        Err(PgnError::IllegalMove {
+            ply: 3,
+            token: "Ke3".to_string(),
+            source: SanError::IllegalMove("Ke3".to_string()),
+        })
+    );
+}
+
+#[test]
+fn rejects_malformed_tags_and_comments() {
+    assert!(matches!(
+        parse_pgn("[White Alice]\n1. e4 *"),
+        Err(PgnError::InvalidTag(_))
+    ));
+    assert_eq!(
+        parse_pgn("1. e4 {never closed"),
+        Err(PgnError::Unterminated)
+    );
+    assert!(matches!(
+        parse_pgn("[FEN \"8/8/8/8/8/8/8/8 w - - 0 1\"]\n*"),
+        Err(PgnError::InvalidSetup(_))
+    ));
+}
+
+fn play(engine: &mut ChessEngine, moves: &[&str]) {
+    for san in moves {
+        let mv = parse_san(engine.state(), san).expect("legal san");
+        engine.apply_move(mv.into()).expect("legal move");
+    }
+}
+
+#[test]
+fn exports_seven_tag_roster_and_result_from_status() {
+    let mut engine = ChessEngine::new_game();
+    play(
+        &mut engine,
+        &["e4", "e5", "Bc4", "Nc6", "Qh5", "Nf6", "Qxf7#"],
+    );
+
+    assert_eq!(
+        engine.to_pgn(),
+        r#"[Event "?"]
+[Site "?"]
+[Date "????.??.??"]
+[Round "?"]
+[White "?"]
+[Black "?"]
+[Result "1-0"]
+
+1. e4 e5 2. Bc4 Nc6 3. Qh5 Nf6 4. Qxf7# 1-0
+"#
+    );
+}
+
+#[test]
+fn exports_setup_header_and_black_first_move_number() {
+    let mut engine = ChessEngine::from_fen("4k3/4p3/8/8/8/8/8/4K3 b - - 0 12").expect("valid fen");
+    play(&mut engine, &["e5", "Kd2"]);
+
+    let mut game = engine.pgn_game();
+    game.tags
+        .push(("White".to_string(), "Alice \"A\"".to_string()));
+    game.tags.push(("Annotator".to_string(), "Bob".to_string()));
+    let pgn = game.to_pgn();
+
+    assert!(pgn.contains("[White \"Alice \\\"A\\\"\"]\n"));
+    assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/4p3/8/8/8/8/8/4K3 b - - 0 12\"]\n"));
+    assert!(pgn.contains("[Annotator \"Bob\"]\n"));
+    assert!(pgn.ends_with("\n12... e5 13. Kd2 *\n"));
+}
+
+#[test]
+fn wraps_movetext_and_round_trips_through_import() {
+    let mut engine = ChessEngine::new_game();
+    play(
+        &mut engine,
+        &[
+            "e4", "e5", "Nf3", "d6", "d4", "Bg4", "dxe5", "Bxf3", "Qxf3", "dxe5", "Bc4", "Nf6",
+            "Qb3", "Qe7", "Nc3", "c6", "Bg5", "b5", "Nxb5", "cxb5", "Bxb5+", "Nbd7", "O-O-O",
+            "Rd8", "Rxd7", "Rxd7", "Rd1", "Qe6", "Bxd7+", "Nxd7", "Qb8+", "Nxb8", "Rd8#",
+        ],
+    );
+
+    let pgn = engine.to_pgn();
+    let movetext = pgn.split("\n\n").nth(1).expect("movetext section");
+    assert!(movetext.lines().count() > 1);
+    assert!(movetext.lines().all(|line| line.len() <= 80));
+
+    assert!(movetext.trim_end().ends_with("17. Rd8# 1-0"));
+
+    let imported = parse_pgn(&pgn).expect("exported pgn should import");
+    assert_eq!(imported.moves, engine.pgn_game().moves);
+    assert_eq!(&imported.state, engine.state());
+}
+
+#[test]
+fn exports_tags_set_on_the_engine() {
+    let mut engine = ChessEngine::new_game();
+    engine.set_tag("Event", "Club Championship");
+    engine.set_tag("White", "Alice");
+    engine.set_tag("White", "Carol");
+    engine.set_tag("Annotator", "Bob");
+    play(&mut engine, &["e4"]);
+
+    let pgn = engine.to_pgn();
+    assert!(pgn.starts_with("[Event \"Club Championship\"]\n"));
+    assert!(pgn.contains("[White \"Carol\"]\n[Black \"?\"]\n"));
+    assert!(pgn.contains("[Annotator \"Bob\"]\n"));
+    assert_eq!(engine.tags().len(), 3);
+}
@@ -0,0 +1,101 @@
+use crate::game::{DrawReason, EngineError, GameStatus, MoveInput};
+use crate::repetition::{capturable_en_passant_target, position_key};
+use crate::{from_fen, ChessEngine, Square};
+
+fn play(engine: &mut ChessEngine, moves: &[&str]) {
+    for uci in moves {
+        let input: MoveInput = uci.parse().expect("valid uci");
+        engine.apply_move(input).expect("legal move");
+    }
+}
+
+const KNIGHT_SHUFFLE: [&str; 4] = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+#[test]
+fn counts_repetitions_of_current_position() {
+    let mut engine = ChessEngine::new_game();
+    assert_eq!(engine.repetition_count(), 1);
+
+    play(&mut engine, &KNIGHT_SHUFFLE);
+    assert_eq!(engine.repetition_count(), 2);
+    assert!(!engine.can_claim_threefold_repetition());
+
+    play(&mut engine, &KNIGHT_SHUFFLE);
+    assert_eq!(engine.repetition_count(), 3);
+    assert!(engine.can_claim_threefold_repetition());
+    assert_eq!(engine.status(), GameStatus::InProgress);
+}
+
+#[test]
+fn fivefold_repetition_ends_the_game() {
+    let mut engine = ChessEngine::new_game();
+    for _ in 0..3 {
+        play(&mut engine, &KNIGHT_SHUFFLE);
+    }
+    play(&mut engine, &KNIGHT_SHUFFLE[..3]);
+    assert_eq!(engine.status(), GameStatus::InProgress);
+
+    let result = engine
+        .apply_move("f6g8".parse().expect("valid uci"))
+        .expect("legal move");
+    let draw = GameStatus::Draw(DrawReason::FivefoldRepetition);
+    assert_eq!(result.state.status, draw);
+    assert_eq!(engine.status(), draw);
+    assert_eq!(
+        engine.apply_move("g1f3".parse().expect("valid uci")),
+        Err(EngineError::GameOver)
+    );
+    assert!(engine.to_pgn().ends_with("1/2-1/2\n"));
+}
+
+#[test]
+fn undo_revives_game_drawn_by_fivefold_repetition() {
+    let mut engine = ChessEngine::new_game();
+    for _ in 0..4 {
+        play(&mut engine, &KNIGHT_SHUFFLE);
+    }
+    assert!(matches!(engine.status(), GameStatus::Draw(_)));
+
+    engine.undo();
+    assert_eq!(engine.status(), GameStatus::InProgress);
+}
+
+#[test]
+fn castling_rights_distinguish_positions() {
+    let mut engine = ChessEngine::new_game();
+    play(
+        &mut engine,
+        &["g1f3", "g8f6", "h1g1", "h8g8", "g1h1", "g8h8"],
+    );
+
+    assert_eq!(
+        engine.state().board,
+        engine.state_at(2).expect("ply 2").board
+    );
+    assert_eq!(engine.repetition_count(), 1);
+}
+
+#[test]
+fn only_capturable_en_passant_targets_count() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4", "g8f6", "g1f3", "f6g8", "f3g1"]);
+    assert_eq!(
+        engine.state_at(1).expect("ply 1").en_passant_target,
+        Square::from_algebraic("e3")
+    );
+    assert_eq!(
+        capturable_en_passant_target(engine.state_at(1).unwrap()),
+        None
+    );
+    assert_eq!(engine.repetition_count(), 2);
+
+    let state = from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").expect("valid fen");
+    assert_eq!(
+        position_key(&state).en_passant_target,
+        Square::from_algebraic("e3")
+    );
+
+    // The d4 pawn is pinned against its king, so the capture is illegal.
+    let pinned = from_fen("8/8/8/8/k2pP2R/8/8/4K3 b - e3 0 1").expect("valid fen");
+    assert_eq!(capturable_en_passant_target(&pinned), None);
+}
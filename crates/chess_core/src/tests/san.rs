@@ -0,0 +1,210 @@
+use crate::board::{PieceColor, PieceKind, Square};
+use crate::game::{GameStatus, Move};
+use crate::san::{parse_san, to_san, SanError};
+use crate::{from_fen, legal_moves, new_game};
+
+fn mv(from: &str, to: &str, promotion: Option<PieceKind>) -> Move {
+    Move {
+        from: Square::from_algebraic(from).expect("valid square"),
+        to: Square::from_algebraic(to).expect("valid square"),
+        promotion,
+    }
+}
+
+#[test]
+fn formats_pawn_and_piece_moves() {
+    let state = new_game();
+    assert_eq!(to_san(&state, &mv("e2", "e4", None)), "e4");
+    assert_eq!(to_san(&state, &mv("g1", "f3", None)), "Nf3");
+}
+
+#[test]
+fn formats_captures_including_en_passant() {
+    let state = from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3")
+        .expect("valid fen");
+    assert_eq!(to_san(&state, &mv("e5", "f6", None)), "exf6");
+
+    let state = from_fen("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2")
+        .expect("valid fen");
+    assert_eq!(to_san(&state, &mv("e4", "d5", None)), "exd5");
+    assert_eq!(to_san(&state, &mv("d1", "h5", None)), "Qh5");
+}
+
+#[test]
+fn disambiguates_by_file_rank_or_both() {
+    let state = from_fen("4k3/8/8/8/8/8/4K3/R6R w - - 0 1").expect("valid fen");
+    assert_eq!(to_san(&state, &mv("a1", "d1", None)), "Rad1");
+
+    let state = from_fen("R7/7k/8/8/8/8/8/R3K3 w - - 0 1").expect("valid fen");
+    assert_eq!(to_san(&state, &mv("a1", "a4", None)), "R1a4");
+
+    let state = from_fen("8/8/8/7k/8/2Q1Q3/8/2Q1K3 w - - 0 1").expect("valid fen");
+    assert_eq!(to_san(&state, &mv("c3", "d2", None)), "Qc3d2");
+
+    // A pinned knight cannot reach the square, so no disambiguation is needed.
+    let state = from_fen("4k3/4r3/8/8/8/8/4N3/2N1K3 w - - 0 1").expect("valid fen");
+    assert_eq!(to_san(&state, &mv("c1", "d3", None)), "Nd3");
+}
+
+#[test]
+fn formats_castling() {
+    let state = from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").expect("valid fen");
+    assert_eq!(to_san(&state, &mv("e1", "g1", None)), "O-O");
+    assert_eq!(to_san(&state, &mv("e1", "c1", None)), "O-O-O");
+}
+
+#[test]
+fn formats_promotion_check_and_mate() {
+    let state = from_fen("8/4P3/8/8/8/8/k7/4K3 w - - 0 1").expect("valid fen");
+    assert_eq!(
+        to_san(&state, &mv("e7", "e8", Some(PieceKind::Queen))),
+        "e8=Q"
+    );
+    assert_eq!(
+        to_san(&state, &mv("e7", "e8", Some(PieceKind::Knight))),
+        "e8=N"
+    );
+
+    let state = from_fen("k7/8/8/8/8/8/8/4K2R w K - 0 1").expect("valid fen");
+    assert_eq!(to_san(&state, &mv("h1", "h8", None)), "Rh8+");
+
+    let state = from_fen("k7/8/1K6/8/8/8/8/7R w - - 0 1").expect("valid fen");
+    assert_eq!(to_san(&state, &mv("h1", "h8", None)), "Rh8#");
+}
+
+#[test]
+fn check_suffix_does_not_depend_on_the_game_status() {
+    // Already drawn by the 75-move rule, so the move could not be applied.
+    let state = from_fen("k7/8/8/8/8/8/8/4K2R w - - 150 120").expect("valid fen");
+    assert!(matches!(state.status, GameStatus::Draw(_)));
+    assert_eq!(to_san(&state, &mv("h1", "h8", None)), "Rh8+");
+
+    let mut state = from_fen("k7/8/1K6/8/8/8/8/7R w - - 0 1").expect("valid fen");
+    state.status = GameStatus::Resigned(PieceColor::Black);
+    assert_eq!(to_san(&state, &mv("h1", "h8", None)), "Rh8#");
+}
+
+#[test]
+fn parses_san_into_legal_moves() {
+    let state = new_game();
+    assert_eq!(parse_san(&state, "e4"), Ok(mv("e2", "e4", None)));
+    assert_eq!(parse_san(&state, "Nf3"), Ok(mv("g1", "f3", None)));
+
+    let state = from_fen("r1bqkbnr/pppp1ppp/2n5/3Pp3/8/8/PPP1PPPP/RNBQKBNR w KQkq e6 0 3")
+        .expect("valid fen");
+    assert_eq!(parse_san(&state, "dxe6"), Ok(mv("d5", "e6", None)));
+    assert_eq!(parse_san(&state, "dxc6"), Ok(mv("d5", "c6", None)));
+}
+
+#[test]
+fn parses_disambiguated_moves_and_reports_ambiguity() {
+    let state = from_fen("4k3/8/8/8/8/8/4K3/R6R w - - 0 1").expect("valid fen");
+    assert_eq!(parse_san(&state, "Rad1"), Ok(mv("a1", "d1", None)));
+    assert_eq!(parse_san(&state, "Rhd1"), Ok(mv("h1", "d1", None)));
+    assert_eq!(
+        parse_san(&state, "Rd1"),
+        Err(SanError::AmbiguousMove("Rd1".to_string()))
+    );
+
+    let state = from_fen("8/8/8/7k/8/2Q1Q3/8/2Q1K3 w - - 0 1").expect("valid fen");
+    assert_eq!(parse_san(&state, "Qc3d2"), Ok(mv("c3", "d2", None)));
+    assert_eq!(
+        parse_san(&state, "Qcd2"),
+        Err(SanError::AmbiguousMove("Qcd2".to_string()))
+    );
+}
+
+#[test]
+fn parses_castling_and_promotion_with_common_sloppiness() {
+    let state = from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").expect("valid fen");
+    assert_eq!(parse_san(&state, "O-O"), Ok(mv("e1", "g1", None)));
+    assert_eq!(parse_san(&state, "0-0-0"), Ok(mv("e1", "c1", None)));
+    assert_eq!(parse_san(&state, "O-O+"), Ok(mv("e1", "g1", None)));
+
+    let state = from_fen("k7/4P3/8/8/8/8/8/4K3 w - - 0 1").expect("valid fen");
+    assert_eq!(
+        parse_san(&state, "e8=N"),
+        Ok(mv("e7", "e8", Some(PieceKind::Knight)))
+    );
+    assert_eq!(
+        parse_san(&state, "e8=q+"),
+        Ok(mv("e7", "e8", Some(PieceKind::Queen)))
+    );
+    assert_eq!(
+        parse_san(&state, "e8R"),
+        Ok(mv("e7", "e8", Some(PieceKind::Rook)))
+    );
+}
+
+#[test]
+fn pawn_moves_without_a_file_are_pushes() {
+    let state = from_fen("4k3/8/3n4/2P5/8/8/8/4K3 w - - 0 1").expect("valid fen");
+    assert_eq!(
+        parse_san(&state, "d6"),
+        Err(SanError::IllegalMove("d6".to_string()))
+    );
+    assert_eq!(parse_san(&state, "cxd6"), Ok(mv("c5", "d6", None)));
+
+    let state = from_fen("k3r3/3P4/8/8/8/8/8/4K3 w - - 0 1").expect("valid fen");
+    assert_eq!(
+        parse_san(&state, "e8=Q"),
+        Err(SanError::IllegalMove("e8=Q".to_string()))
+    );
+    assert_eq!(
+        parse_san(&state, "dxe8=Q"),
+        Ok(mv("d7", "e8", Some(PieceKind::Queen)))
+    );
+}
+
+#[test]
+fn capture_marker_needs_a_capture() {
+    let state = new_game();
+    assert_eq!(
+        parse_san(&state, "Nxf3"),
+        Err(SanError::IllegalMove("Nxf3".to_string()))
+    );
+    assert_eq!(
+        parse_san(&state, "exe4"),
+        Err(SanError::IllegalMove("exe4".to_string()))
+    );
+
+    let state = from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").expect("valid fen");
+    assert_eq!(parse_san(&state, "exd6"), Ok(mv("e5", "d6", None)));
+}
+
+#[test]
+fn distinguishes_invalid_syntax_from_illegal_moves() {
+    let state = new_game();
+    assert_eq!(parse_san(&state, ""), Err(SanError::Empty));
+    assert_eq!(
+        parse_san(&state, "Zz9"),
+        Err(SanError::InvalidSyntax("Zz9".to_string()))
+    );
+    assert_eq!(
+        parse_san(&state, "e5"),
+        Err(SanError::IllegalMove("e5".to_string()))
+    );
+    assert_eq!(
+        parse_san(&state, "O-O"),
+        Err(SanError::IllegalMove("O-O".to_string()))
+    );
+    assert_eq!(
+        parse_san(&state, "e4=Q"),
+        Err(SanError::IllegalMove("e4=Q".to_string()))
+    );
+}
+
+#[test]
+fn parse_san_round_trips_to_san() {
+    let state = from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+        .expect("valid fen");
+
+    for rank in 0u8..8 {
+        for file in 0u8..8 {
+            for legal in legal_moves(&state, Square { file, rank }) {
+                let san = to_san(&state, &legal);
+                assert_eq!(parse_san(&state, &san), Ok(legal), "{san}");
+            }
+        }
+    }
+}
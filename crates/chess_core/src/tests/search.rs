@@ -0,0 +1,138 @@
+use std::cell::Cell;
+
+use crate::search::{search, SearchLimits, MATE_SCORE};
+use crate::{apply_move, from_fen, new_game, ChessEngine, MoveInput, TranspositionTable};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn no_clock() -> u64 {
+    0
+}
+
+#[test]
+fn finds_mate_in_one() {
+    let state = from_fen("r1bqkbnr/pppp1ppp/2n5/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4")
+        .expect("valid fen");
+    let result = search(&state, SearchLimits::depth(3), no_clock);
+
+    assert_eq!(result.best_move.as_ref().expect("a move").to_uci(), "h5f7");
+    assert_eq!(result.score, MATE_SCORE - 1);
+    assert_eq!(result.mate_in(), Some(1));
+    // The mate is proven at depth 1, so deeper iterations are skipped.
+    assert_eq!(result.depth, 1);
+}
+
+#[test]
+fn finds_mate_in_two_with_full_pv() {
+    let state = from_fen("r2qkb1r/pp2nppp/3p4/2pNN1B1/2BnP3/3P4/PPP2PPP/R2bK2R w KQkq - 1 10")
+        .expect("valid fen");
+    let result = search(&state, SearchLimits::depth(4), no_clock);
+
+    assert_eq!(result.mate_in(), Some(2));
+    assert_eq!(result.pv.len(), 3);
+    assert_eq!(result.best_move.as_ref(), result.pv.first());
+
+    let mut replay = state;
+    for mv in &result.pv {
+        replay = apply_move(&replay, mv.clone().into())
+            .expect("pv move is legal")
+            .state;
+    }
+    assert!(matches!(
+        replay.status,
+        crate::GameStatus::Checkmate(crate::PieceColor::White)
+    ));
+}
+
+#[test]
+fn reports_being_mated_as_negative_mate() {
+    // Kg8 is forced and Ra8 mates.
+    let state = from_fen("7k/8/6K1/8/8/8/8/R7 b - - 0 1").expect("valid fen");
+    let result = search(&state, SearchLimits::depth(4), no_clock);
+
+    assert_eq!(result.best_move.as_ref().expect("a move").to_uci(), "h8g8");
+    assert_eq!(result.score, -(MATE_SCORE - 2));
+    assert_eq!(result.mate_in(), Some(-1));
+}
+
+#[test]
+fn wins_hanging_material() {
+    let state = from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1").expect("valid fen");
+    let result = search(&state, SearchLimits::depth(2), no_clock);
+
+    assert_eq!(result.best_move.as_ref().expect("a move").to_uci(), "d1d5");
+    assert!(result.score >= 400);
+}
+
+#[test]
+fn game_over_has_no_best_move() {
+    let state = from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").expect("valid fen");
+    let result = search(&state, SearchLimits::depth(3), no_clock);
+
+    assert_eq!(result.best_move, None);
+    assert!(result.pv.is_empty());
+    assert_eq!(result.depth, 0);
+}
+
+#[test]
+fn stops_at_the_depth_limit() {
+    let result = search(&new_game(), SearchLimits::depth(3), no_clock);
+
+    assert_eq!(result.depth, 3);
+    assert!(result.nodes > 0);
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn stops_at_the_node_limit() {
+    let result = search(&new_game(), SearchLimits::nodes(5_000), no_clock);
+
+    assert!(result.best_move.is_some());
+    assert!(result.depth >= 1);
+    assert_eq!(result.nodes, 5_000);
+}
+
+#[test]
+fn small_node_limits_cut_the_first_iteration_short() {
+    let state = from_fen(KIWIPETE).expect("valid fen");
+    for limit in [1, 10, 100] {
+        let result = search(&state, SearchLimits::nodes(limit), no_clock);
+
+        assert_eq!(result.nodes, limit);
+        assert_eq!(result.depth, 0);
+        assert_eq!(result.pv.first(), result.best_move.as_ref());
+        let best = result.best_move.expect("a move");
+        assert!(crate::legal_moves_all(&state).contains(&best));
+    }
+}
+
+#[test]
+fn stops_when_the_clock_runs_out() {
+    // Each reading advances a fake clock by one millisecond.
+    let now = Cell::new(0);
+    let clock = || {
+        now.set(now.get() + 1);
+        now.get()
+    };
+    let result = search(&new_game(), SearchLimits::time_ms(20), clock);
+
+    assert!(result.best_move.is_some());
+    assert!(result.depth < 10);
+}
+
+#[test]
+fn engine_search_plays_from_the_current_position() {
+    let mut engine = ChessEngine::new_game();
+    engine
+        .apply_move(MoveInput::from_uci("e2e4").expect("valid uci"))
+        .expect("legal move");
+    let mut table = TranspositionTable::new(1);
+    let result = engine.search(SearchLimits::depth(2), &mut table, no_clock);
+    let best = result.best_move.expect("a move");
+
+    assert_eq!(
+        engine.state().board[best.from.rank as usize][best.from.file as usize]
+            .map(|piece| piece.color),
+        Some(crate::PieceColor::Black)
+    );
+}
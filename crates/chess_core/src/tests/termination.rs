@@ -0,0 +1,182 @@
+use crate::board::PieceColor;
+use crate::game::{DrawReason, EngineError, GameResult, GameStatus, MoveInput, Termination};
+use crate::{apply_move, from_fen, ChessEngine};
+
+fn play(engine: &mut ChessEngine, moves: &[&str]) {
+    for uci in moves {
+        let input: MoveInput = uci.parse().expect("valid uci");
+        engine.apply_move(input).expect("legal move");
+    }
+}
+
+fn draw(termination: Termination) -> GameResult {
+    GameResult {
+        winner: None,
+        termination,
+    }
+}
+
+#[test]
+fn status_maps_to_result() {
+    assert_eq!(GameStatus::InProgress.result(), None);
+    assert_eq!(
+        GameStatus::Checkmate(PieceColor::Black).result(),
+        Some(GameResult {
+            winner: Some(PieceColor::Black),
+            termination: Termination::Checkmate,
+        })
+    );
+    assert_eq!(
+        GameStatus::Draw(DrawReason::InsufficientMaterial).result(),
+        Some(draw(Termination::InsufficientMaterial))
+    );
+}
+
+#[test]
+fn resignation_ends_the_game_for_the_opponent() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4"]);
+
+    let result = engine.resign(PieceColor::Black).expect("game in progress");
+    assert_eq!(
+        result,
+        GameResult {
+            winner: Some(PieceColor::White),
+            termination: Termination::Resignation,
+        }
+    );
+    assert_eq!(engine.status(), GameStatus::Resigned(PieceColor::Black));
+    assert_eq!(engine.result(), Some(result));
+    assert_eq!(
+        engine.apply_move("e7e5".parse().expect("valid uci")),
+        Err(EngineError::GameOver)
+    );
+    assert_eq!(engine.resign(PieceColor::White), Err(EngineError::GameOver));
+    assert!(engine.to_pgn().ends_with("1. e4 1-0\n"));
+}
+
+#[test]
+fn draw_offer_can_be_accepted_by_the_opponent_only() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4"]);
+
+    assert_eq!(
+        engine.accept_draw(PieceColor::Black),
+        Err(EngineError::NoDrawOffer)
+    );
+    engine
+        .offer_draw(PieceColor::White)
+        .expect("game in progress");
+    assert_eq!(
+        engine.accept_draw(PieceColor::White),
+        Err(EngineError::NoDrawOffer)
+    );
+
+    let result = engine
+        .accept_draw(PieceColor::Black)
+        .expect("pending offer");
+    assert_eq!(result, draw(Termination::DrawAgreement));
+    assert_eq!(engine.status(), GameStatus::Draw(DrawReason::Agreement));
+}
+
+#[test]
+fn moving_declines_a_pending_draw_offer() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4"]);
+    engine
+        .offer_draw(PieceColor::White)
+        .expect("game in progress");
+
+    play(&mut engine, &["e7e5"]);
+    assert_eq!(engine.draw_offer(), None);
+    assert_eq!(
+        engine.accept_draw(PieceColor::Black),
+        Err(EngineError::NoDrawOffer)
+    );
+
+    // An offer made before moving survives the offering side's own move.
+    engine
+        .offer_draw(PieceColor::White)
+        .expect("game in progress");
+    play(&mut engine, &["g1f3"]);
+    assert_eq!(engine.draw_offer(), Some(PieceColor::White));
+
+    engine
+        .decline_draw(PieceColor::Black)
+        .expect("pending offer");
+    assert_eq!(engine.draw_offer(), None);
+}
+
+#[test]
+fn claim_draw_validates_threefold_repetition() {
+    let mut engine = ChessEngine::new_game();
+    assert_eq!(engine.claim_draw(), Err(EngineError::InvalidDrawClaim));
+
+    for _ in 0..2 {
+        play(&mut engine, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    }
+
+    assert_eq!(
+        engine.claim_draw(),
+        Ok(draw(Termination::ThreefoldRepetition))
+    );
+    assert_eq!(engine.claim_draw(), Err(EngineError::GameOver));
+}
+
+#[test]
+fn claim_draw_validates_fifty_move_rule() {
+    let mut engine = ChessEngine::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 98 80").expect("valid fen");
+    play(&mut engine, &["a1a2"]);
+    assert_eq!(engine.claim_draw(), Err(EngineError::InvalidDrawClaim));
+
+    play(&mut engine, &["e8e7"]);
+    assert_eq!(engine.claim_draw(), Ok(draw(Termination::FiftyMoveRule)));
+    assert_eq!(engine.status(), GameStatus::Draw(DrawReason::FiftyMoveRule));
+}
+
+#[test]
+fn undo_reopens_a_resigned_game() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4", "e7e5"]);
+    engine.resign(PieceColor::White).expect("game in progress");
+
+    engine.undo();
+    assert_eq!(engine.status(), GameStatus::InProgress);
+    play(&mut engine, &["e7e6"]);
+    assert_eq!(engine.history().len(), 2);
+}
+
+#[test]
+fn moving_after_mate_reports_game_over() {
+    let state = from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 1 1").expect("valid fen");
+    assert_eq!(state.status, GameStatus::Checkmate(PieceColor::White));
+    assert_eq!(
+        apply_move(&state, "g8h8".parse().expect("valid uci")),
+        Err(EngineError::GameOver)
+    );
+}
+
+#[test]
+fn resigning_before_the_first_move_keeps_the_start_position() {
+    let mut engine = ChessEngine::new_game();
+    engine.resign(PieceColor::White).expect("game in progress");
+
+    assert_eq!(engine.status(), GameStatus::Resigned(PieceColor::White));
+    assert_eq!(engine.state_at(0), Some(&crate::new_game()));
+    let pgn = engine.to_pgn();
+    assert!(!pgn.contains("[SetUp"));
+    assert!(!pgn.contains("[FEN"));
+    assert!(pgn.ends_with("0-1\n"));
+}
+
+#[test]
+fn undo_after_resigning_and_a_new_move_discards_the_resignation() {
+    let mut engine = ChessEngine::new_game();
+    play(&mut engine, &["e2e4"]);
+    engine.resign(PieceColor::Black).expect("game in progress");
+
+    engine.undo();
+    play(&mut engine, &["d2d4"]);
+    assert_eq!(engine.status(), GameStatus::InProgress);
+    assert_eq!(engine.history()[0].state.status, GameStatus::InProgress);
+}
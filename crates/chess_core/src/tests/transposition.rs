@@ -0,0 +1,126 @@
+use crate::search::{search_with_table, SearchLimits, MATE_SCORE};
+use crate::transposition::{Bound, TableEntry, TranspositionTable};
+use crate::{from_fen, Move};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn no_clock() -> u64 {
+    0
+}
+
+fn uci(mv: &str) -> Option<Move> {
+    Some(mv.parse().expect("valid uci"))
+}
+
+#[test]
+fn capacity_fits_in_the_requested_size() {
+    let table = TranspositionTable::new(1);
+    let entry_size = std::mem::size_of::<Option<TableEntry>>();
+    assert!(table.capacity().is_power_of_two());
+    assert!(table.capacity() * entry_size <= 1024 * 1024);
+    assert!(table.capacity() * entry_size * 2 > 1024 * 1024);
+    assert_eq!(TranspositionTable::new(2).capacity(), table.capacity() * 2);
+    assert_eq!(TranspositionTable::new(0).capacity(), 1);
+}
+
+#[test]
+fn stored_entries_can_be_probed() {
+    let mut table = TranspositionTable::new(1);
+    table.store(42, 5, Bound::Lower, 120, 0, uci("e2e4"));
+
+    let entry = table.probe(42, 0).expect("entry");
+    assert_eq!(entry.depth, 5);
+    assert_eq!(entry.bound, Bound::Lower);
+    assert_eq!(entry.score, 120);
+    assert_eq!(entry.best_move, uci("e2e4"));
+}
+
+#[test]
+fn probe_checks_the_full_key() {
+    let mut table = TranspositionTable::new(1);
+    let colliding = 42 + table.capacity() as u64;
+    table.store(42, 5, Bound::Exact, 0, 0, None);
+
+    assert!(table.probe(colliding, 0).is_none());
+}
+
+#[test]
+fn mate_scores_are_relative_to_the_probing_ply() {
+    let mut table = TranspositionTable::new(1);
+    // Found at ply 3: mate five plies from the root, two from this node.
+    table.store(7, 4, Bound::Exact, MATE_SCORE - 5, 3, None);
+    table.store(8, 4, Bound::Exact, -(MATE_SCORE - 5), 3, None);
+
+    assert_eq!(table.probe(7, 1).expect("entry").score, MATE_SCORE - 3);
+    assert_eq!(table.probe(8, 1).expect("entry").score, -(MATE_SCORE - 3));
+    assert_eq!(table.probe(7, 3).expect("entry").score, MATE_SCORE - 5);
+}
+
+#[test]
+fn deeper_entries_survive_shallower_ones_from_the_same_search() {
+    let mut table = TranspositionTable::new(1);
+    let colliding = 42 + table.capacity() as u64;
+    table.store(42, 6, Bound::Exact, 10, 0, None);
+    table.store(colliding, 2, Bound::Exact, 20, 0, None);
+
+    assert!(table.probe(42, 0).is_some());
+    assert!(table.probe(colliding, 0).is_none());
+}
+
+#[test]
+fn entries_from_an_earlier_search_are_replaced() {
+    let mut table = TranspositionTable::new(1);
+    let colliding = 42 + table.capacity() as u64;
+    table.store(42, 6, Bound::Exact, 10, 0, None);
+    table.new_search();
+    table.store(colliding, 2, Bound::Exact, 20, 0, None);
+
+    assert!(table.probe(42, 0).is_none());
+    assert_eq!(table.probe(colliding, 0).expect("entry").score, 20);
+}
+
+#[test]
+fn same_position_keeps_its_best_move() {
+    let mut table = TranspositionTable::new(1);
+    table.store(42, 6, Bound::Lower, 10, 0, uci("g1f3"));
+    table.store(42, 2, Bound::Upper, -5, 0, None);
+
+    let entry = table.probe(42, 0).expect("entry");
+    assert_eq!(entry.depth, 2);
+    assert_eq!(entry.bound, Bound::Upper);
+    assert_eq!(entry.best_move, uci("g1f3"));
+}
+
+#[test]
+fn clear_forgets_everything() {
+    let mut table = TranspositionTable::new(1);
+    table.store(42, 6, Bound::Exact, 10, 0, None);
+    table.clear();
+    assert!(table.probe(42, 0).is_none());
+}
+
+#[test]
+fn warm_table_saves_work_on_the_next_search() {
+    let state = from_fen(KIWIPETE).expect("valid fen");
+    let mut table = TranspositionTable::new(4);
+
+    let first = search_with_table(&state, &[], SearchLimits::depth(3), &mut table, no_clock);
+    let second = search_with_table(&state, &[], SearchLimits::depth(3), &mut table, no_clock);
+
+    assert!(second.nodes < first.nodes);
+    assert_eq!(second.score, first.score);
+    assert_eq!(second.best_move, first.best_move);
+}
+
+#[test]
+fn mates_are_still_found_through_the_table() {
+    let state = from_fen("r2qkb1r/pp2nppp/3p4/2pNN1B1/2BnP3/3P4/PPP2PPP/R2bK2R w KQkq - 1 10")
+        .expect("valid fen");
+    let mut table = TranspositionTable::new(1);
+
+    for _ in 0..2 {
+        let result = search_with_table(&state, &[], SearchLimits::depth(4), &mut table, no_clock);
+        assert_eq!(result.mate_in(), Some(2));
+        assert_eq!(result.pv.len(), 3);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::board::{PieceKind, Square};
+use crate::game::{EngineError, Move, MoveInput};
+use crate::{apply_move, new_game};
+
+fn square(name: &str) -> Square {
+    Square::from_algebraic(name).expect("valid square")
+}
+
+#[test]
+fn formats_moves_as_uci() {
+    let quiet = Move {
+        from: square("e2"),
+        to: square("e4"),
+        promotion: None,
+    };
+    let promotion = Move {
+        from: square("e7"),
+        to: square("e8"),
+        promotion: Some(PieceKind::Queen),
+    };
+
+    assert_eq!(quiet.to_uci(), "e2e4");
+    assert_eq!(promotion.to_uci(), "e7e8q");
+    assert_eq!(promotion.to_string(), "e7e8q");
+}
+
+#[test]
+fn parses_uci_into_move_input() {
+    assert_eq!(
+        MoveInput::from_uci("g1f3"),
+        Ok(MoveInput {
+            from: square("g1"),
+            to: square("f3"),
+            promotion: None,
+        })
+    );
+    assert_eq!(
+        "a2a1n".parse::<MoveInput>(),
+        Ok(MoveInput {
+            from: square("a2"),
+            to: square("a1"),
+            promotion: Some(PieceKind::Knight),
+        })
+    );
+}
+
+#[test]
+fn move_display_and_parse_round_trip() {
+    for uci in ["e2e4", "e7e8q", "a2a1n", "e1g1"] {
+        let mv: Move = uci.parse().expect("valid uci");
+        assert_eq!(mv.to_string(), uci);
+        assert_eq!(Move::from_uci(uci), Ok(mv));
+    }
+    assert_eq!(
+        "e2e9".parse::<Move>(),
+        Err(EngineError::InvalidUci("e2e9".to_string()))
+    );
+}
+
+#[test]
+fn rejects_malformed_uci() {
+    for uci in ["", "e2", "e2e9", "i2e4", "e7e8k", "e7e8qq", "e2-e4"] {
+        assert_eq!(
+            MoveInput::from_uci(uci),
+            Err(EngineError::InvalidUci(uci.to_string())),
+            "{uci}"
+        );
+    }
+}
+
+#[test]
+fn uci_round_trips_through_apply_move() {
+    let input: MoveInput = "e2e4".parse().expect("valid uci");
+    let result = apply_move(&new_game(), input.clone()).expect("legal move");
+
+    assert_eq!(result.move_applied.to_uci(), input.to_string());
+    assert_eq!(MoveInput::from(result.move_applied), input);
+}
@@ -0,0 +1,138 @@
+use crate::board::{empty_board, Piece, PieceColor, PieceKind, Square};
+use crate::game::{EngineError, GameState, GameStatus};
+use crate::validation::PositionError;
+use crate::{from_fen, new_game, CastlingRights};
+
+fn custom_state(active_color: PieceColor) -> GameState {
+    GameState {
+        board: empty_board(),
+        active_color,
+        status: GameStatus::InProgress,
+        halfmove_clock: 0,
+        fullmove_number: 1,
+        castling_rights: CastlingRights {
+            white_king_side: false,
+            white_queen_side: false,
+            black_king_side: false,
+            black_queen_side: false,
+        },
+        en_passant_target: None,
+    }
+}
+
+fn set_piece(state: &mut GameState, square: &str, color: PieceColor, kind: PieceKind) {
+    let square = Square::from_algebraic(square).expect("valid square");
+    state.board[square.rank as usize][square.file as usize] = Some(Piece { color, kind });
+}
+
+fn square(name: &str) -> Square {
+    Square::from_algebraic(name).expect("valid square")
+}
+
+#[test]
+fn start_position_is_valid() {
+    assert!(new_game().validate().is_empty());
+}
+
+#[test]
+fn reports_missing_and_extra_kings() {
+    let mut state = custom_state(PieceColor::White);
+    set_piece(&mut state, "a1", PieceColor::White, PieceKind::King);
+    set_piece(&mut state, "c1", PieceColor::White, PieceKind::King);
+    set_piece(&mut state, "e1", PieceColor::White, PieceKind::King);
+
+    let errors = state.validate();
+    assert!(errors.contains(&PositionError::TooManyKings(PieceColor::White)));
+    assert!(errors.contains(&PositionError::MissingKing(PieceColor::Black)));
+}
+
+#[test]
+fn reports_pawns_on_back_ranks() {
+    let mut state = custom_state(PieceColor::White);
+    set_piece(&mut state, "e1", PieceColor::White, PieceKind::King);
+    set_piece(&mut state, "e8", PieceColor::Black, PieceKind::King);
+    set_piece(&mut state, "a1", PieceColor::White, PieceKind::Pawn);
+    set_piece(&mut state, "h8", PieceColor::Black, PieceKind::Pawn);
+
+    assert_eq!(
+        state.validate(),
+        vec![
+            PositionError::PawnOnBackRank(square("a1")),
+            PositionError::PawnOnBackRank(square("h8")),
+        ]
+    );
+}
+
+#[test]
+fn reports_side_not_to_move_in_check() {
+    let mut state = custom_state(PieceColor::White);
+    set_piece(&mut state, "e1", PieceColor::White, PieceKind::King);
+    set_piece(&mut state, "e8", PieceColor::Black, PieceKind::King);
+    set_piece(&mut state, "e4", PieceColor::White, PieceKind::Rook);
+
+    assert_eq!(
+        state.validate(),
+        vec![PositionError::InactiveSideInCheck(PieceColor::Black)]
+    );
+}
+
+#[test]
+fn reports_castling_rights_without_rook_or_king() {
+    let mut state = custom_state(PieceColor::White);
+    set_piece(&mut state, "e1", PieceColor::White, PieceKind::King);
+    set_piece(&mut state, "h1", PieceColor::White, PieceKind::Rook);
+    set_piece(&mut state, "d8", PieceColor::Black, PieceKind::King);
+    set_piece(&mut state, "a8", PieceColor::Black, PieceKind::Rook);
+    state.castling_rights = CastlingRights::standard();
+
+    assert_eq!(
+        state.validate(),
+        vec![
+            PositionError::InvalidCastlingRight {
+                color: PieceColor::White,
+                king_side: false,
+            },
+            PositionError::InvalidCastlingRight {
+                color: PieceColor::Black,
+                king_side: true,
+            },
+            PositionError::InvalidCastlingRight {
+                color: PieceColor::Black,
+                king_side: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn reports_en_passant_target_without_double_push() {
+    let mut state = custom_state(PieceColor::White);
+    set_piece(&mut state, "e1", PieceColor::White, PieceKind::King);
+    set_piece(&mut state, "e8", PieceColor::Black, PieceKind::King);
+    set_piece(&mut state, "d5", PieceColor::Black, PieceKind::Pawn);
+    state.en_passant_target = Some(square("d6"));
+    assert!(state.validate().is_empty());
+
+    state.en_passant_target = Some(square("c6"));
+    assert_eq!(
+        state.validate(),
+        vec![PositionError::InvalidEnPassantTarget(square("c6"))]
+    );
+}
+
+#[test]
+fn from_fen_rejects_illegal_positions() {
+    let result = from_fen("4k3/8/8/8/8/8/8/R7 w - - 0 1");
+    assert_eq!(
+        result,
+        Err(EngineError::IllegalPosition(vec![
+            PositionError::MissingKing(PieceColor::White),
+        ]))
+    );
+
+    let result = from_fen("4k3/8/8/8/8/8/8/4RK2 w - - 0 1");
+    assert_eq!(
+        result.map_err(|err| err.to_string()),
+        Err("illegal position: Black is in check but it is not their move".to_string())
+    );
+}
@@ -0,0 +1,118 @@
+use crate::game::GameState;
+use crate::move_gen::{generate_legal_moves, make_move, unmake_move};
+use crate::zobrist::{hash, hash_after_move};
+use crate::{from_fen, ChessEngine, MoveInput};
+
+// Reference keys from the Polyglot book format specification.
+const POLYGLOT_KEYS: [(&str, u64); 9] = [
+    (
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        0x463b96181691fc9c,
+    ),
+    (
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        0x823c9b50fd114196,
+    ),
+    (
+        "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+        0x0756b94461c50fb0,
+    ),
+    (
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2",
+        0x662fafb965db29d4,
+    ),
+    (
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        0x22a48b5a8e47ff78,
+    ),
+    (
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 0 3",
+        0x652a607ca3f242c1,
+    ),
+    (
+        "rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 0 4",
+        0x00fdd303c946bdd9,
+    ),
+    (
+        "rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3",
+        0x3c8123ea7b067637,
+    ),
+    (
+        "rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 0 4",
+        0x5c3f9b829b279560,
+    ),
+];
+
+#[test]
+fn matches_polyglot_reference_keys() {
+    for (fen, key) in POLYGLOT_KEYS {
+        let state = from_fen(fen).expect("valid fen");
+        assert_eq!(hash(&state), key, "{fen}");
+    }
+}
+
+fn play(engine: &mut ChessEngine, uci: &str) {
+    engine
+        .apply_move(MoveInput::from_uci(uci).expect("valid uci"))
+        .expect("legal move");
+}
+
+#[test]
+fn engine_tracks_the_reference_games() {
+    let mut engine = ChessEngine::new_game();
+    for (ply, uci) in ["e2e4", "d7d5", "e4e5", "f7f5", "e1e2", "e8f7"]
+        .into_iter()
+        .enumerate()
+    {
+        play(&mut engine, uci);
+        assert_eq!(engine.zobrist_hash(), POLYGLOT_KEYS[ply + 1].1, "{uci}");
+    }
+
+    let mut engine = ChessEngine::new_game();
+    for uci in ["a2a4", "b7b5", "h2h4", "b5b4", "c2c4"] {
+        play(&mut engine, uci);
+    }
+    assert_eq!(engine.zobrist_hash(), POLYGLOT_KEYS[7].1);
+    play(&mut engine, "b4c3");
+    play(&mut engine, "a1a3");
+    assert_eq!(engine.zobrist_hash(), POLYGLOT_KEYS[8].1);
+}
+
+#[test]
+fn engine_hash_follows_undo_and_redo() {
+    let mut engine = ChessEngine::new_game();
+    let start = engine.zobrist_hash();
+    for uci in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+        play(&mut engine, uci);
+    }
+    assert_eq!(engine.zobrist_hash(), start);
+
+    engine.undo();
+    assert_eq!(engine.zobrist_hash(), hash(engine.state()));
+    engine.redo();
+    assert_eq!(engine.zobrist_hash(), start);
+}
+
+fn assert_incremental_matches(state: &mut GameState, depth: u32) {
+    let before = hash(state);
+    for mv in generate_legal_moves(state) {
+        let undo = make_move(state, &mv).expect("legal move");
+        assert_eq!(hash_after_move(before, state, &undo), hash(state), "{mv}");
+        if depth > 1 {
+            assert_incremental_matches(state, depth - 1);
+        }
+        unmake_move(state, undo);
+    }
+}
+
+#[test]
+fn incremental_update_matches_full_hash() {
+    for fen in [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ] {
+        let mut state = from_fen(fen).expect("valid fen");
+        assert_incremental_matches(&mut state, 3);
+    }
+}
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::Move;
+use crate::search::{is_mate_score, MAX_PLY};
+use crate::zobrist::ZobristHash;
+
+pub const DEFAULT_SIZE_MB: usize = 16;
+
+const BYTES_PER_MB: usize = 1024 * 1024;
+
+// How the stored score relates to the true score of the position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Bound {
+    Exact,
+    // The search failed high: the true score is at least `score`.
+    Lower,
+    // The search failed low: the true score is at most `score`.
+    Upper,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableEntry {
+    pub key: ZobristHash,
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u8,
+    pub bound: Bound,
+    generation: u8,
+}
+
+// Mate scores are stored as distance from the node rather than from the root,
+// so they stay correct when the position is reached at a different ply.
+fn score_to_table(score: i32, ply: usize) -> i32 {
+    if !is_mate_score(score) {
+        score
+    } else if score > 0 {
+        score + ply as i32
+    } else {
+        score - ply as i32
+    }
+}
+
+fn score_from_table(score: i32, ply: usize) -> i32 {
+    if !is_mate_score(score) {
+        score
+    } else if score > 0 {
+        score - ply as i32
+    } else {
+        score + ply as i32
+    }
+}
+
+// One entry per slot, indexed by the low bits of the hash. A slot is overwritten
+// by the same position, by anything once its entry is from an earlier search,
+// and otherwise only by a search at least as deep.
+#[derive(Debug, Clone)]
+pub struct TranspositionTable {
+    entries: Vec<Option<TableEntry>>,
+    generation: u8,
+}
+
+impl TranspositionTable {
+    // The entry count is rounded down to a power of two that fits in `size_mb`.
+    pub fn new(size_mb: usize) -> Self {
+        let count = (size_mb * BYTES_PER_MB / std::mem::size_of::<Option<TableEntry>>()).max(1);
+        Self {
+            entries: vec![None; 1 << count.ilog2()],
+            generation: 0,
+        }
+    }
+
+    pub fn resize(&mut self, size_mb: usize) {
+        *self = Self::new(size_mb);
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    // Forgets every position, e.g. when a new game starts.
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+        self.generation = 0;
+    }
+
+    // Marks existing entries as stale so they give way to the coming search.
+    pub fn new_search(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    fn index(&self, key: ZobristHash) -> usize {
+        key as usize & (self.entries.len() - 1)
+    }
+
+    // Scores come back relative to the root of a search at `ply`.
+    pub fn probe(&self, key: ZobristHash, ply: usize) -> Option<TableEntry> {
+        let entry = self.entries[self.index(key)].as_ref()?;
+        if entry.key != key {
+            return None;
+        }
+        Some(TableEntry {
+            score: score_from_table(entry.score, ply),
+            ..entry.clone()
+        })
+    }
+
+    pub fn store(
+        &mut self,
+        key: ZobristHash,
+        depth: u32,
+        bound: Bound,
+        score: i32,
+        ply: usize,
+        best_move: Option<Move>,
+    ) {
+        let generation = self.generation;
+        let depth = depth.min(MAX_PLY as u32) as u8;
+        let index = self.index(key);
+        let slot = &mut self.entries[index];
+
+        let best_move = match slot {
+            Some(existing) if existing.key == key => best_move.or(existing.best_move.take()),
+            Some(existing) if existing.generation == generation && existing.depth > depth => {
+                return;
+            }
+            _ => best_move,
+        };
+
+        *slot = Some(TableEntry {
+            key,
+            best_move,
+            score: score_to_table(score, ply),
+            depth,
+            bound,
+            generation,
+        });
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIZE_MB)
+    }
+}
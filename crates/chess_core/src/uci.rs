@@ -0,0 +1,106 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::{PieceKind, Square};
+use crate::game::{EngineError, Move, MoveInput};
+
+pub fn promotion_from_char(c: char) -> Option<PieceKind> {
+    match PieceKind::from_char(c)? {
+        PieceKind::Pawn | PieceKind::King => None,
+        kind => Some(kind),
+    }
+}
+
+fn format_uci(from: Square, to: Square, promotion: Option<PieceKind>) -> String {
+    let mut uci = format!("{}{}", from.to_algebraic(), to.to_algebraic());
+    if let Some(kind) = promotion {
+        uci.push(kind.to_char());
+    }
+    uci
+}
+
+impl Move {
+    // Checks the notation only; whether the move is legal depends on a position.
+    pub fn from_uci(uci: &str) -> Result<Self, EngineError> {
+        MoveInput::from_uci(uci).map(Self::from)
+    }
+
+    pub fn to_uci(&self) -> String {
+        format_uci(self.from, self.to, self.promotion)
+    }
+}
+
+impl MoveInput {
+    pub fn from_uci(uci: &str) -> Result<Self, EngineError> {
+        let invalid = || EngineError::InvalidUci(uci.to_string());
+        let text = uci.trim();
+        if !text.is_ascii() || !(4..=5).contains(&text.len()) {
+            return Err(invalid());
+        }
+
+        let from = Square::from_algebraic(&text[0..2]).ok_or_else(invalid)?;
+        let to = Square::from_algebraic(&text[2..4]).ok_or_else(invalid)?;
+        let promotion = match text[4..].chars().next() {
+            Some(c) => Some(promotion_from_char(c).ok_or_else(invalid)?),
+            None => None,
+        };
+
+        Ok(Self {
+            from,
+            to,
+            promotion,
+        })
+    }
+
+    pub fn to_uci(&self) -> String {
+        format_uci(self.from, self.to, self.promotion)
+    }
+}
+
+impl From<Move> for MoveInput {
+    fn from(mv: Move) -> Self {
+        Self {
+            from: mv.from,
+            to: mv.to,
+            promotion: mv.promotion,
+        }
+    }
+}
+
+impl From<MoveInput> for Move {
+    fn from(input: MoveInput) -> Self {
+        Self {
+            from: input.from,
+            to: input.to,
+            promotion: input.promotion,
+        }
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_uci())
+    }
+}
+
+impl fmt::Display for MoveInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_uci())
+    }
+}
+
+impl FromStr for Move {
+    type Err = EngineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_uci(s)
+    }
+}
+
+impl FromStr for MoveInput {
+    type Err = EngineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_uci(s)
+    }
+}
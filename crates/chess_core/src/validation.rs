@@ -0,0 +1,125 @@
+use thiserror::Error;
+
+use crate::board::{PieceColor, PieceKind, Square};
+use crate::game::GameState;
+use crate::move_gen::{is_in_check_for_color, opposite};
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PositionError {
+    #[error("{0:?} has no king")]
+    MissingKing(PieceColor),
+    #[error("{0:?} has more than one king")]
+    TooManyKings(PieceColor),
+    #[error("{0:?} has more than 8 pawns")]
+    TooManyPawns(PieceColor),
+    #[error("{0:?} has more than 16 pieces")]
+    TooManyPieces(PieceColor),
+    #[error("pawn on back rank at {}", .0.to_algebraic())]
+    PawnOnBackRank(Square),
+    #[error("{0:?} is in check but it is not their move")]
+    InactiveSideInCheck(PieceColor),
+    #[error("{color:?} {} castling right without king and rook on their home squares", if *.king_side { "king-side" } else { "queen-side" })]
+    InvalidCastlingRight { color: PieceColor, king_side: bool },
+    #[error("en passant target {} does not follow a double pawn push", .0.to_algebraic())]
+    InvalidEnPassantTarget(Square),
+}
+
+pub fn validate_position(state: &GameState) -> Vec<PositionError> {
+    let mut errors = Vec::new();
+
+    for color in [PieceColor::White, PieceColor::Black] {
+        let count = |kind: PieceKind| {
+            state
+                .board
+                .iter()
+                .flatten()
+                .flatten()
+                .filter(|piece| piece.color == color && piece.kind == kind)
+                .count()
+        };
+        let total = state
+            .board
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|piece| piece.color == color)
+            .count();
+
+        match count(PieceKind::King) {
+            0 => errors.push(PositionError::MissingKing(color)),
+            1 => {}
+            _ => errors.push(PositionError::TooManyKings(color)),
+        }
+
+        if count(PieceKind::Pawn) > 8 {
+            errors.push(PositionError::TooManyPawns(color));
+        }
+
+        if total > 16 {
+            errors.push(PositionError::TooManyPieces(color));
+        }
+    }
+
+    for rank in [0u8, 7] {
+        for file in 0u8..8 {
+            if state.board[rank as usize][file as usize]
+                .is_some_and(|piece| piece.kind == PieceKind::Pawn)
+            {
+                errors.push(PositionError::PawnOnBackRank(Square { file, rank }));
+            }
+        }
+    }
+
+    let inactive = opposite(state.active_color);
+    if is_in_check_for_color(state, inactive) {
+        errors.push(PositionError::InactiveSideInCheck(inactive));
+    }
+
+    validate_castling_rights(state, &mut errors);
+
+    if let Some(target) = state.en_passant_target {
+        if !is_valid_en_passant_target(state, target) {
+            errors.push(PositionError::InvalidEnPassantTarget(target));
+        }
+    }
+
+    errors
+}
+
+fn has_piece(state: &GameState, file: u8, rank: u8, color: PieceColor, kind: PieceKind) -> bool {
+    state.board[rank as usize][file as usize]
+        .is_some_and(|piece| piece.color == color && piece.kind == kind)
+}
+
+fn validate_castling_rights(state: &GameState, errors: &mut Vec<PositionError>) {
+    let rights = state.castling_rights;
+    let checks = [
+        (PieceColor::White, true, rights.white_king_side, 0, 7),
+        (PieceColor::White, false, rights.white_queen_side, 0, 0),
+        (PieceColor::Black, true, rights.black_king_side, 7, 7),
+        (PieceColor::Black, false, rights.black_queen_side, 7, 0),
+    ];
+
+    for (color, king_side, allowed, rank, rook_file) in checks {
+        if allowed
+            && !(has_piece(state, 4, rank, color, PieceKind::King)
+                && has_piece(state, rook_file, rank, color, PieceKind::Rook))
+        {
+            errors.push(PositionError::InvalidCastlingRight { color, king_side });
+        }
+    }
+}
+
+fn is_valid_en_passant_target(state: &GameState, target: Square) -> bool {
+    // The pushed pawn belongs to the side that just moved.
+    let (target_rank, pawn_rank, origin_rank) = match state.active_color {
+        PieceColor::White => (5, 4, 6),
+        PieceColor::Black => (2, 3, 1),
+    };
+    let pushed_color = opposite(state.active_color);
+
+    target.rank == target_rank
+        && state.board[target.rank as usize][target.file as usize].is_none()
+        && state.board[origin_rank][target.file as usize].is_none()
+        && has_piece(state, target.file, pawn_rank, pushed_color, PieceKind::Pawn)
+}
@@ -1,20 +1,39 @@
+use chess_core::san::to_san;
+use chess_core::transposition::DEFAULT_SIZE_MB;
+use chess_core::uci::promotion_from_char;
 use chess_core::{
-    apply_move, from_fen, legal_moves, new_game, GameState, MoveInput, PieceKind, Square,
+    evaluate, from_fen, to_fen, ChessEngine, EngineError, FenError, Move, MoveInput, PieceColor,
+    PieceKind, PositionError, SearchLimits, Square, TranspositionTable,
 };
 use wasm_bindgen::prelude::*;
 
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    fn date_now() -> f64;
+}
+
 fn parse_square(square: &str) -> Result<Square, JsValue> {
     Square::from_algebraic(square).ok_or_else(|| JsValue::from_str("invalid square"))
 }
 
+fn parse_color(color: &str) -> Result<PieceColor, JsValue> {
+    match color {
+        "white" | "White" | "w" => Ok(PieceColor::White),
+        "black" | "Black" | "b" => Ok(PieceColor::Black),
+        _ => Err(JsValue::from_str("invalid color")),
+    }
+}
+
 fn parse_promotion(promotion: Option<String>) -> Result<Option<PieceKind>, JsValue> {
-    match promotion.as_deref() {
-        None => Ok(None),
-        Some("q") | Some("Q") => Ok(Some(PieceKind::Queen)),
-        Some("r") | Some("R") => Ok(Some(PieceKind::Rook)),
-        Some("b") | Some("B") => Ok(Some(PieceKind::Bishop)),
-        Some("n") | Some("N") => Ok(Some(PieceKind::Knight)),
-        Some(_) => Err(JsValue::from_str("invalid promotion piece")),
+    let Some(promotion) = promotion else {
+        return Ok(None);
+    };
+
+    let mut chars = promotion.chars();
+    match (chars.next().and_then(promotion_from_char), chars.next()) {
+        (Some(kind), None) => Ok(Some(kind)),
+        _ => Err(JsValue::from_str("invalid promotion piece")),
     }
 }
 
@@ -23,34 +42,147 @@ fn to_js_value<T: serde::Serialize>(value: &T) -> Result<JsValue, JsValue> {
         .map_err(|err| JsValue::from_str(&format!("serialization error: {}", err)))
 }
 
+fn fen_error_code(err: &FenError) -> &'static str {
+    match err {
+        FenError::WrongFieldCount { .. } => "wrong_field_count",
+        FenError::WrongRankCount { .. } => "wrong_rank_count",
+        FenError::InvalidPiece { .. } => "invalid_piece",
+        FenError::RankOverflow { .. } => "rank_overflow",
+        FenError::RankUnderflow { .. } => "rank_underflow",
+        FenError::InvalidSideToMove { .. } => "invalid_side_to_move",
+        FenError::InvalidCastling { .. } => "invalid_castling",
+        FenError::InvalidEnPassant { .. } => "invalid_en_passant",
+        FenError::InvalidClock { .. } => "invalid_clock",
+    }
+}
+
+fn color_name(color: PieceColor) -> &'static str {
+    match color {
+        PieceColor::White => "white",
+        PieceColor::Black => "black",
+    }
+}
+
+fn position_problem(err: &PositionError) -> WasmPositionProblem {
+    let (code, color, square, king_side) = match err {
+        PositionError::MissingKing(color) => ("missing_king", Some(*color), None, None),
+        PositionError::TooManyKings(color) => ("too_many_kings", Some(*color), None, None),
+        PositionError::TooManyPawns(color) => ("too_many_pawns", Some(*color), None, None),
+        PositionError::TooManyPieces(color) => ("too_many_pieces", Some(*color), None, None),
+        PositionError::PawnOnBackRank(square) => ("pawn_on_back_rank", None, Some(*square), None),
+        PositionError::InactiveSideInCheck(color) => {
+            ("inactive_side_in_check", Some(*color), None, None)
+        }
+        PositionError::InvalidCastlingRight { color, king_side } => (
+            "invalid_castling_right",
+            Some(*color),
+            None,
+            Some(*king_side),
+        ),
+        PositionError::InvalidEnPassantTarget(square) => {
+            ("invalid_en_passant_target", None, Some(*square), None)
+        }
+    };
+
+    WasmPositionProblem {
+        code,
+        message: err.to_string(),
+        color: color.map(color_name),
+        square: square.map(|square| square.to_algebraic()),
+        king_side,
+    }
+}
+
+// Lists what makes a well-formed FEN an illegal position, so a board editor can
+// mark each problem. Only a FEN that cannot be parsed at all is an error.
+#[wasm_bindgen]
+pub fn validate(fen: String) -> Result<JsValue, JsValue> {
+    let problems = match from_fen(&fen) {
+        Ok(_) => Vec::new(),
+        Err(EngineError::IllegalPosition(errors)) => errors.iter().map(position_problem).collect(),
+        Err(err) => return Err(engine_error_to_js(err)),
+    };
+    to_js_value(&problems)
+}
+
+fn engine_error_to_js(err: EngineError) -> JsValue {
+    match &err {
+        EngineError::InvalidFen(fen_err) => {
+            let payload = WasmFenError {
+                code: fen_error_code(fen_err),
+                message: err.to_string(),
+                field: fen_err.field(),
+                character: fen_err.character().map(String::from),
+            };
+            to_js_value(&payload).unwrap_or_else(|fallback| fallback)
+        }
+        EngineError::IllegalPosition(errors) => {
+            let payload = WasmPositionError {
+                code: "illegal_position",
+                message: err.to_string(),
+                errors: errors.iter().map(ToString::to_string).collect(),
+            };
+            to_js_value(&payload).unwrap_or_else(|fallback| fallback)
+        }
+        _ => JsValue::from_str(&err.to_string()),
+    }
+}
+
 #[wasm_bindgen]
 pub struct WasmGame {
-    state: GameState,
+    engine: ChessEngine,
+    // Kept across searches in this game, and only allocated by the first one.
+    table: Option<TranspositionTable>,
+    hash_size_mb: usize,
+}
+
+impl Default for WasmGame {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[wasm_bindgen]
 impl WasmGame {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        Self { state: new_game() }
+        Self {
+            engine: ChessEngine::new_game(),
+            table: None,
+            hash_size_mb: DEFAULT_SIZE_MB,
+        }
     }
 
     #[wasm_bindgen(js_name = fromFen)]
     pub fn from_fen_js(fen: String) -> Result<WasmGame, JsValue> {
-        let parsed = from_fen(&fen).map_err(|err| JsValue::from_str(&err.to_string()))?;
-        Ok(Self { state: parsed })
+        let engine = ChessEngine::from_fen(&fen).map_err(engine_error_to_js)?;
+        Ok(Self {
+            engine,
+            table: None,
+            hash_size_mb: DEFAULT_SIZE_MB,
+        })
     }
 
     #[wasm_bindgen(js_name = legalMoves)]
     pub fn legal_moves_js(&self, square: String) -> Result<JsValue, JsValue> {
         let square = parse_square(&square)?;
-        let moves = legal_moves(&self.state, square)
-            .into_iter()
-            .map(|m| WasmMove {
-                from: m.from.to_algebraic(),
-                to: m.to.to_algebraic(),
-                promotion: m.promotion.map(|p| format!("{:?}", p).to_lowercase()),
-            })
+        let moves = self
+            .engine
+            .legal_moves(square)
+            .iter()
+            .map(WasmMove::from_move)
+            .collect::<Vec<_>>();
+
+        to_js_value(&moves)
+    }
+
+    #[wasm_bindgen(js_name = allLegalMoves)]
+    pub fn all_legal_moves_js(&self) -> Result<JsValue, JsValue> {
+        let moves = self
+            .engine
+            .legal_moves_all()
+            .iter()
+            .map(WasmMove::from_move)
             .collect::<Vec<_>>();
 
         to_js_value(&moves)
@@ -67,28 +199,258 @@ impl WasmGame {
         let to = parse_square(&to)?;
         let promotion = parse_promotion(promotion)?;
 
-        let result = apply_move(
-            &self.state,
-            MoveInput {
-                from,
-                to,
-                promotion,
-            },
-        )
-        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        self.apply_input(MoveInput {
+            from,
+            to,
+            promotion,
+        })
+    }
+
+    #[wasm_bindgen(js_name = applyUci)]
+    pub fn apply_uci_js(&mut self, uci: String) -> Result<JsValue, JsValue> {
+        let input = MoveInput::from_uci(&uci).map_err(engine_error_to_js)?;
+        self.apply_input(input)
+    }
+
+    pub fn undo(&mut self) -> bool {
+        self.engine.undo().is_some()
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.engine.redo().is_some()
+    }
+
+    #[wasm_bindgen(js_name = gotoPly)]
+    pub fn goto_ply_js(&mut self, ply: usize) -> Result<JsValue, JsValue> {
+        self.engine.goto_ply(ply).map_err(engine_error_to_js)?;
+        to_js_value(self.engine.state())
+    }
+
+    pub fn ply(&self) -> usize {
+        self.engine.ply()
+    }
+
+    pub fn history(&self) -> Result<JsValue, JsValue> {
+        let entries = self
+            .engine
+            .history()
+            .iter()
+            .enumerate()
+            .map(|(index, result)| {
+                let before = self
+                    .engine
+                    .state_at(index)
+                    .expect("every recorded ply has a preceding state");
+                WasmHistoryEntry {
+                    ply: index + 1,
+                    uci: result.move_applied.to_uci(),
+                    san: to_san(before, &result.move_applied),
+                    fen: to_fen(&result.state),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        to_js_value(&entries)
+    }
+
+    #[wasm_bindgen(js_name = repetitionCount)]
+    pub fn repetition_count_js(&self) -> usize {
+        self.engine.repetition_count()
+    }
+
+    #[wasm_bindgen(js_name = canClaimThreefoldRepetition)]
+    pub fn can_claim_threefold_repetition_js(&self) -> bool {
+        self.engine.can_claim_threefold_repetition()
+    }
 
-        self.state = result.state;
-        to_js_value(&self.state)
+    #[wasm_bindgen(js_name = canClaimFiftyMoveRule)]
+    pub fn can_claim_fifty_move_rule_js(&self) -> bool {
+        self.engine.can_claim_fifty_move_rule()
+    }
+
+    pub fn resign(&mut self, color: String) -> Result<JsValue, JsValue> {
+        let result = self
+            .engine
+            .resign(parse_color(&color)?)
+            .map_err(engine_error_to_js)?;
+        to_js_value(&result)
+    }
+
+    pub fn timeout(&mut self, color: String) -> Result<JsValue, JsValue> {
+        let result = self
+            .engine
+            .timeout(parse_color(&color)?)
+            .map_err(engine_error_to_js)?;
+        to_js_value(&result)
+    }
+
+    #[wasm_bindgen(js_name = offerDraw)]
+    pub fn offer_draw_js(&mut self, color: String) -> Result<(), JsValue> {
+        self.engine
+            .offer_draw(parse_color(&color)?)
+            .map_err(engine_error_to_js)
+    }
+
+    #[wasm_bindgen(js_name = acceptDraw)]
+    pub fn accept_draw_js(&mut self, color: String) -> Result<JsValue, JsValue> {
+        let result = self
+            .engine
+            .accept_draw(parse_color(&color)?)
+            .map_err(engine_error_to_js)?;
+        to_js_value(&result)
+    }
+
+    #[wasm_bindgen(js_name = declineDraw)]
+    pub fn decline_draw_js(&mut self, color: String) -> Result<(), JsValue> {
+        self.engine
+            .decline_draw(parse_color(&color)?)
+            .map_err(engine_error_to_js)
+    }
+
+    #[wasm_bindgen(js_name = claimDraw)]
+    pub fn claim_draw_js(&mut self) -> Result<JsValue, JsValue> {
+        let result = self.engine.claim_draw().map_err(engine_error_to_js)?;
+        to_js_value(&result)
+    }
+
+    pub fn result(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.engine.result())
+    }
+
+    // Limits left undefined do not apply, but at least one is required so the
+    // search cannot run unbounded on the page's thread.
+    pub fn search(
+        &mut self,
+        depth: Option<u32>,
+        nodes: Option<u32>,
+        time_ms: Option<u32>,
+    ) -> Result<JsValue, JsValue> {
+        if depth.is_none() && nodes.is_none() && time_ms.is_none() {
+            return Err(JsValue::from_str(
+                "search needs a depth, node or time limit",
+            ));
+        }
+        let limits = SearchLimits {
+            depth,
+            nodes: nodes.map(u64::from),
+            time_ms: time_ms.map(u64::from),
+        };
+        let size_mb = self.hash_size_mb;
+        let table = self
+            .table
+            .get_or_insert_with(|| TranspositionTable::new(size_mb));
+        let result = self.engine.search(limits, table, || date_now() as u64);
+
+        to_js_value(&WasmSearchResult {
+            best_move: result.best_move.as_ref().map(Move::to_uci),
+            score: result.score,
+            mate_in: result.mate_in(),
+            pv: result.pv.iter().map(Move::to_uci).collect(),
+            depth: result.depth,
+            nodes: result.nodes,
+        })
+    }
+
+    #[wasm_bindgen(js_name = setHashSize)]
+    pub fn set_hash_size_js(&mut self, size_mb: usize) {
+        self.hash_size_mb = size_mb;
+        if let Some(table) = &mut self.table {
+            table.resize(size_mb);
+        }
+    }
+
+    #[wasm_bindgen(js_name = clearHash)]
+    pub fn clear_hash_js(&mut self) {
+        if let Some(table) = &mut self.table {
+            table.clear();
+        }
+    }
+
+    // Static evaluation of the current position, term by term.
+    pub fn evaluate(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&evaluate(self.engine.state()))
+    }
+
+    #[wasm_bindgen(js_name = setTag)]
+    pub fn set_tag_js(&mut self, name: String, value: String) {
+        self.engine.set_tag(&name, &value);
+    }
+
+    #[wasm_bindgen(js_name = toPgn)]
+    pub fn to_pgn_js(&self) -> String {
+        self.engine.to_pgn()
     }
 
     pub fn state(&self) -> Result<JsValue, JsValue> {
-        to_js_value(&self.state)
+        to_js_value(self.engine.state())
+    }
+}
+
+impl WasmGame {
+    // Returns the new state together with the move's details.
+    fn apply_input(&mut self, input: MoveInput) -> Result<JsValue, JsValue> {
+        let result = self.engine.apply_move(input).map_err(engine_error_to_js)?;
+        to_js_value(&result)
     }
 }
 
 #[derive(serde::Serialize)]
 struct WasmMove {
+    uci: String,
     from: String,
     to: String,
     promotion: Option<String>,
 }
+
+impl WasmMove {
+    fn from_move(m: &Move) -> Self {
+        Self {
+            uci: m.to_uci(),
+            from: m.from.to_algebraic(),
+            to: m.to.to_algebraic(),
+            promotion: m.promotion.map(|p| format!("{:?}", p).to_lowercase()),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct WasmHistoryEntry {
+    ply: usize,
+    uci: String,
+    san: String,
+    fen: String,
+}
+
+#[derive(serde::Serialize)]
+struct WasmSearchResult {
+    best_move: Option<String>,
+    score: i32,
+    mate_in: Option<i32>,
+    pv: Vec<String>,
+    depth: u32,
+    nodes: u64,
+}
+
+#[derive(serde::Serialize)]
+struct WasmFenError {
+    code: &'static str,
+    message: String,
+    field: usize,
+    character: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct WasmPositionProblem {
+    code: &'static str,
+    message: String,
+    color: Option<&'static str>,
+    square: Option<String>,
+    king_side: Option<bool>,
+}
+
+#[derive(serde::Serialize)]
+struct WasmPositionError {
+    code: &'static str,
+    message: String,
+    errors: Vec<String>,
+}
@@ -13,10 +13,22 @@ export type MoveDTO = {
   promotion?: PieceKind;
 };
 
+export type DrawReasonDTO =
+  | "agreement"
+  | "threefold_repetition"
+  | "fivefold_repetition"
+  | "fifty_move_rule"
+  | "seventy_five_move_rule"
+  | "insufficient_material"
+  | "timeout_vs_insufficient_material";
+
 export type GameStatusDTO =
   | { type: "in_progress" }
   | { type: "checkmate"; winner: PieceColor }
-  | { type: "stalemate"; sideToMove: PieceColor };
+  | { type: "stalemate"; sideToMove: PieceColor }
+  | { type: "draw"; reason: DrawReasonDTO }
+  | { type: "resigned"; loser: PieceColor }
+  | { type: "timeout"; loser: PieceColor };
 
 export type CastlingRightsDTO = {
   whiteKingSide: boolean;
@@ -34,3 +46,27 @@ export type GameStateDTO = {
   castlingRights: CastlingRightsDTO;
   enPassantTarget: string | null;
 };
+
+export type MoveFlagsDTO = {
+  capture: boolean;
+  enPassant: boolean;
+  castling: boolean;
+  doublePush: boolean;
+  promotion: boolean;
+};
+
+export type MoveDetailsDTO = {
+  movedPiece: PieceDTO;
+  capturedPiece: PieceDTO | null;
+  capturedSquare: string | null;
+  castleRookFrom: string | null;
+  castleRookTo: string | null;
+  givesCheck: boolean;
+  givesMate: boolean;
+  flags: MoveFlagsDTO;
+};
+
+export type MoveResultDTO = {
+  state: GameStateDTO;
+  details: MoveDetailsDTO;
+};
@@ -4,8 +4,12 @@ export type {
   PieceDTO,
   BoardMapDTO,
   MoveDTO,
+  DrawReasonDTO,
   GameStatusDTO,
   CastlingRightsDTO,
-  GameStateDTO
+  GameStateDTO,
+  MoveFlagsDTO,
+  MoveDetailsDTO,
+  MoveResultDTO
 } from "./game-state";
 export type { BotAdapter } from "./bot";
@@ -1,10 +1,18 @@
-import type { GameStateDTO, MoveDTO, PieceColor } from "@chess/contracts";
+import type {
+  DrawReasonDTO,
+  GameStateDTO,
+  MoveDetailsDTO,
+  MoveDTO,
+  MoveResultDTO,
+  PieceColor,
+  PieceDTO
+} from "@chess/contracts";
 
 export type WasmMoveDTO = MoveDTO;
 
 export interface WasmGameLike {
   legalMoves(square: string): WasmMoveDTO[];
-  applyMove(from: string, to: string, promotion?: MoveDTO["promotion"]): GameStateDTO;
+  applyMove(from: string, to: string, promotion?: MoveDTO["promotion"]): MoveResultDTO;
   state(): GameStateDTO;
 }
 
@@ -158,18 +166,77 @@ function toBoardMap(rawBoard: unknown): GameStateDTO["board"] {
         continue;
       }
 
-      const piece = maybePiece as RawPiece;
       const square = `${FILES[file]}${rank + 1}`;
-      board[square] = {
-        color: toPieceColor(piece.color),
-        kind: String(piece.kind).toLowerCase() as NonNullable<GameStateDTO["board"][string]>["kind"]
-      };
+      board[square] = toPiece(maybePiece as RawPiece);
     }
   }
 
   return board;
 }
 
+function toPiece(piece: RawPiece): PieceDTO {
+  return {
+    color: toPieceColor(piece.color),
+    kind: String(piece.kind).toLowerCase() as PieceDTO["kind"]
+  };
+}
+
+function toOptionalPiece(raw: unknown): PieceDTO | null {
+  return raw && typeof raw === "object" ? toPiece(raw as RawPiece) : null;
+}
+
+function toOptionalSquare(raw: unknown): string | null {
+  return raw && typeof raw === "object" ? toAlgebraicSquare(raw as RawSquare) : null;
+}
+
+function toMoveDetailsDTO(rawDetails: unknown): MoveDetailsDTO {
+  const details: Record<string, unknown> =
+    rawDetails && typeof rawDetails === "object" ? (rawDetails as Record<string, unknown>) : {};
+  const flags: Record<string, unknown> =
+    details.flags && typeof details.flags === "object"
+      ? (details.flags as Record<string, unknown>)
+      : {};
+
+  return {
+    movedPiece: toPiece((details.moved_piece ?? {}) as RawPiece),
+    capturedPiece: toOptionalPiece(details.captured_piece),
+    capturedSquare: toOptionalSquare(details.captured_square),
+    castleRookFrom: toOptionalSquare(details.castle_rook_from),
+    castleRookTo: toOptionalSquare(details.castle_rook_to),
+    givesCheck: Boolean(details.gives_check),
+    givesMate: Boolean(details.gives_mate),
+    flags: {
+      capture: Boolean(flags.capture),
+      enPassant: Boolean(flags.en_passant),
+      castling: Boolean(flags.castling),
+      doublePush: Boolean(flags.double_push),
+      promotion: Boolean(flags.promotion)
+    }
+  };
+}
+
+function toDrawReason(input: unknown): DrawReasonDTO {
+  switch (input) {
+    case "Agreement":
+      return "agreement";
+    case "ThreefoldRepetition":
+      return "threefold_repetition";
+    case "FivefoldRepetition":
+      return "fivefold_repetition";
+    case "FiftyMoveRule":
+      return "fifty_move_rule";
+    case "SeventyFiveMoveRule":
+      return "seventy_five_move_rule";
+    case "InsufficientMaterial":
+      return "insufficient_material";
+    case "TimeoutVsInsufficientMaterial":
+      return "timeout_vs_insufficient_material";
+    default:
+      // A reason this adapter does not know would otherwise be misreported.
+      throw new Error(`Unknown draw reason: ${String(input)}`);
+  }
+}
+
 function toGameStatus(rawStatus: unknown): GameStateDTO["status"] {
   if (rawStatus === "in_progress") {
     return { type: "in_progress" };
@@ -202,6 +269,15 @@ function toGameStatus(rawStatus: unknown): GameStateDTO["status"] {
   if ("Stalemate" in statusRecord) {
     return { type: "stalemate", sideToMove: toPieceColor(statusRecord.Stalemate) };
   }
+  if ("Draw" in statusRecord) {
+    return { type: "draw", reason: toDrawReason(statusRecord.Draw) };
+  }
+  if ("Resigned" in statusRecord) {
+    return { type: "resigned", loser: toPieceColor(statusRecord.Resigned) };
+  }
+  if ("Timeout" in statusRecord) {
+    return { type: "timeout", loser: toPieceColor(statusRecord.Timeout) };
+  }
 
   return { type: "in_progress" };
 }
@@ -306,9 +382,14 @@ class WasmBoundGame implements WasmGameLike {
     return toMoveList(this.game.legalMoves(square));
   }
 
-  applyMove(from: string, to: string, promotion?: MoveDTO["promotion"]): GameStateDTO {
-    const rawState = this.game.applyMove(from, to, toWasmPromotion(promotion));
-    return toGameStateDTO(rawState);
+  applyMove(from: string, to: string, promotion?: MoveDTO["promotion"]): MoveResultDTO {
+    const rawResult = this.game.applyMove(from, to, toWasmPromotion(promotion));
+    const result: Record<string, unknown> =
+      rawResult && typeof rawResult === "object" ? (rawResult as Record<string, unknown>) : {};
+    return {
+      state: toGameStateDTO(result.state),
+      details: toMoveDetailsDTO(result.details)
+    };
   }
 
   state(): GameStateDTO {
@@ -409,17 +490,15 @@ class FallbackWasmGame implements WasmGameLike {
       .map((to) => ({ from: square, to }));
   }
 
-  applyMove(from: string, to: string, promotion?: MoveDTO["promotion"]): GameStateDTO {
+  applyMove(from: string, to: string, promotion?: MoveDTO["promotion"]): MoveResultDTO {
     const legal = this.legalMoves(from).some((m) => m.to === to);
-    if (!legal) {
-      return this.gameState;
-    }
-
     const piece = this.gameState.board[from];
-    if (!piece) {
-      return this.gameState;
+    if (!legal || !piece) {
+      // Matches the wasm engine, which throws on an illegal move.
+      throw new Error(`Illegal move: ${from} -> ${to}`);
     }
 
+    const captured = this.gameState.board[to] ?? null;
     const board = { ...this.gameState.board };
     delete board[from];
     board[to] = {
@@ -439,7 +518,25 @@ class FallbackWasmGame implements WasmGameLike {
       enPassantTarget: null
     };
 
-    return this.gameState;
+    return {
+      state: this.gameState,
+      details: {
+        movedPiece: piece,
+        capturedPiece: captured,
+        capturedSquare: captured ? to : null,
+        castleRookFrom: null,
+        castleRookTo: null,
+        givesCheck: false,
+        givesMate: false,
+        flags: {
+          capture: captured !== null,
+          enPassant: false,
+          castling: false,
+          doublePush: false,
+          promotion: promotion !== undefined
+        }
+      }
+    };
   }
 
   state(): GameStateDTO {
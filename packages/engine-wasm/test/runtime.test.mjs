@@ -11,9 +11,12 @@ test("createWasmGame uses wasm runtime semantics from starting position", async
 
   assert.deepEqual(legalTargets, ["e3", "e4"]);
 
-  const nextState = game.applyMove("e2", "e4");
+  const { state: nextState, details } = game.applyMove("e2", "e4");
   assert.equal(nextState.activeColor, "black");
   assert.equal(nextState.enPassantTarget, "e3");
+  assert.deepEqual(details.movedPiece, { color: "white", kind: "pawn" });
+  assert.equal(details.flags.doublePush, true);
+  assert.equal(details.flags.capture, false);
 });
 
 test("createWasmGame supports promotion option flow", async () => {
@@ -42,7 +45,8 @@ test("createWasmGame supports promotion option flow", async () => {
 
   assert.deepEqual(promotionMoves, ["bishop", "knight", "queen", "rook"]);
 
-  const promotedState = game.applyMove("b7", "b8", "rook");
+  const { state: promotedState, details } = game.applyMove("b7", "b8", "rook");
+  assert.equal(details.flags.promotion, true);
   assert.equal(promotedState.board.b8?.kind, "rook");
   assert.equal(promotedState.board.b8?.color, "white");
   assert.equal(promotedState.activeColor, "black");
@@ -64,7 +68,10 @@ test("createWasmGame applies king-side castling correctly", async () => {
     game.applyMove(from, to);
   }
 
-  const stateAfterCastle = game.applyMove("e1", "g1");
+  const { state: stateAfterCastle, details } = game.applyMove("e1", "g1");
+  assert.equal(details.flags.castling, true);
+  assert.equal(details.castleRookFrom, "h1");
+  assert.equal(details.castleRookTo, "f1");
 
   assert.equal(stateAfterCastle.board.g1?.kind, "king");
   assert.equal(stateAfterCastle.board.g1?.color, "white");
@@ -94,7 +101,10 @@ test("createWasmGame applies en passant correctly", async () => {
     .sort();
   assert.ok(legalTargets.includes("d6"));
 
-  const stateAfterEnPassant = game.applyMove("e5", "d6");
+  const { state: stateAfterEnPassant, details } = game.applyMove("e5", "d6");
+  assert.equal(details.flags.enPassant, true);
+  assert.equal(details.capturedSquare, "d5");
+  assert.deepEqual(details.capturedPiece, { color: "black", kind: "pawn" });
   assert.equal(stateAfterEnPassant.board.d6?.kind, "pawn");
   assert.equal(stateAfterEnPassant.board.d6?.color, "white");
   assert.equal(stateAfterEnPassant.board.d5, undefined);